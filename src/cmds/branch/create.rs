@@ -0,0 +1,45 @@
+use anyhow::{anyhow, Context, Result};
+
+use crate::config;
+use crate::config::OutputFormat;
+use crate::gitlab::{api, Client, CreateBranch, Query};
+use crate::utils;
+
+pub fn create_branch_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+
+    let from = match args.value_of("ref") {
+        Some(r) => r,
+        None => config.defaultbranch.as_deref()
+            .ok_or_else(|| anyhow!("Could not determine default remote branch - pass --ref or try `git lab project refresh`"))?,
+    };
+
+    let endpoint = CreateBranch::builder()
+        .project(project_id)
+        .branch(args.value_of("name").unwrap())
+        .ref_(from)
+        .build()
+        .map_err(|e| anyhow!("Could not construct branch creation query.\n {}", e))?;
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    match config.format {
+        Some(OutputFormat::JSON) => {
+            let raw_json = api::raw(endpoint)
+                .query(&gitlabclient)
+                .context("Failed to create branch")?;
+
+            println!("{}", String::from_utf8(raw_json).unwrap());
+            Ok(())
+        }
+
+        Some(OutputFormat::Text) => {
+            api::ignore(endpoint)
+                .query(&gitlabclient)
+                .context("Failed to create branch")?;
+            Ok(())
+        }
+        _ => Err(anyhow!("Bad output format in config")),
+    }
+}
@@ -0,0 +1,27 @@
+use anyhow::{anyhow, Context, Result};
+
+use crate::config;
+use crate::gitlab::{api, Client, DeleteBranch, Query};
+use crate::utils;
+
+pub fn delete_branches_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+
+    debug!("args: {:#?}", args);
+
+    for name in args.values_of("names").unwrap() {
+        let endpoint = DeleteBranch::builder()
+            .project(project_id)
+            .branch(name)
+            .build()
+            .map_err(|e| anyhow!("Could not construct branch deletion query.\n {}", e))?;
+
+        debug!("endpoint: {:#?}", endpoint);
+
+        api::ignore(endpoint)
+            .query(&gitlabclient)
+            .with_context(|| format!("Failed to delete branch {}", name))?;
+    }
+
+    Ok(())
+}
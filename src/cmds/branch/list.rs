@@ -0,0 +1,165 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Local, Utc};
+use chrono_humanize::HumanTime;
+use clap::value_t_or_exit;
+use comfy_table::*;
+use serde_json::{Map, Value};
+
+use crate::cmds::branch::{Branch, ProtectedBranch};
+use crate::config;
+use crate::config::OutputFormat;
+use crate::gitlab::{api, Branches, Client, ProtectedBranches, Query};
+use crate::utils;
+
+fn new_table(headers: &[&str], no_headers: bool) -> Table {
+    let mut table = Table::new();
+
+    table
+        .load_preset("                   ")
+        .set_content_arrangement(ContentArrangement::Dynamic);
+
+    if !no_headers {
+        table.add_row(headers.iter().map(|h| Cell::new(h).set_alignment(CellAlignment::Center)));
+    }
+
+    table
+}
+
+fn yes_no(b: bool) -> Cell {
+    Cell::new(if b { "y" } else { "n" }).set_alignment(CellAlignment::Center)
+}
+
+fn print_branches(branches: Vec<Branch>, no_headers: bool, human: bool) {
+    let mut table = new_table(&["NAME", "DEFAULT", "PROTECTED", "MERGED", "COMMIT", "COMMITTED ON"], no_headers);
+
+    for b in branches {
+        let committed = match b.commit["committed_date"].as_str().map(|d| d.parse::<DateTime<Utc>>()) {
+            Some(Ok(d)) if human => HumanTime::from(d).to_string(),
+            Some(Ok(d)) => DateTime::<Local>::from(d).format("%Y-%m-%d %H:%M:%S").to_string(),
+            _ => "-".to_string(),
+        };
+
+        let mut r = vec![
+            Cell::new(&b.name),
+            yes_no(b.default),
+            yes_no(b.protected),
+            yes_no(b.merged),
+            Cell::new(b.commit["short_id"].as_str().unwrap_or("-")),
+            Cell::new(committed),
+        ];
+
+        if b.default {
+            r = r.into_iter().map(|c| c.add_attribute(Attribute::Bold)).collect();
+        } else if b.merged {
+            r = r.into_iter().map(|c| c.add_attribute(Attribute::Dim)).collect();
+        }
+
+        table.add_row(r);
+    }
+    println!("{}", table);
+}
+
+fn print_protected_branches(branches: Vec<ProtectedBranch>, no_headers: bool) {
+    let mut table = new_table(&["NAME", "PUSH", "MERGE"], no_headers);
+
+    let levels = |l: &[Map<String, Value>]| l
+        .iter()
+        .filter_map(|a| a["access_level_description"].as_str())
+        .collect::<Vec<&str>>()
+        .join(", ");
+
+    for b in branches {
+        table.add_row(vec![
+            Cell::new(&b.name),
+            Cell::new(levels(&b.push_access_levels)),
+            Cell::new(levels(&b.merge_access_levels)),
+        ]);
+    }
+    println!("{}", table);
+}
+
+fn list_protected_branches(
+    args: &clap::ArgMatches,
+    project_id: u64,
+    config: &config::Config,
+    gitlabclient: &Client,
+) -> Result<()> {
+    let max = value_t_or_exit!(args, "max", u32);
+
+    let endpoint = ProtectedBranches::builder()
+        .project(project_id)
+        .build()
+        .map_err(|e| anyhow!("Could not construct protected branches query.\n {}", e))?;
+
+    debug!("endpoint: {:#?}", endpoint);
+
+    match config.format {
+        Some(OutputFormat::JSON) => {
+            let raw_json = api::raw(endpoint)
+                .query(gitlabclient)
+                .context("Failed to query protected branches")?;
+
+            println!("{}", String::from_utf8(raw_json).unwrap());
+            Ok(())
+        }
+
+        Some(OutputFormat::Text) => {
+            let branches: Vec<ProtectedBranch> = api::paged(endpoint, api::Pagination::Limit(max as usize))
+                .query(gitlabclient)
+                .context("Failed to query protected branches")?;
+
+            print_protected_branches(branches, args.occurrences_of("no_headers") > 0);
+            Ok(())
+        }
+        _ => Err(anyhow!("Bad output format in config")),
+    }
+}
+
+pub fn list_branches_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+
+    debug!("args: {:#?}", args);
+
+    if args.is_present("protected") {
+        return list_protected_branches(&args, project_id, &config, &gitlabclient)
+    }
+
+    let max = value_t_or_exit!(args, "max", u32);
+
+    let mut b = Branches::builder();
+    b.project(project_id);
+    if let Some(filter) = args.value_of("filter") {
+        b.search(filter);
+    }
+
+    let endpoint = b
+        .build()
+        .map_err(|e| anyhow!("Could not construct branches query.\n {}", e))?;
+
+    debug!("endpoint: {:#?}", endpoint);
+
+    match config.format {
+        Some(OutputFormat::JSON) => {
+            let raw_json = api::raw(endpoint)
+                .query(&gitlabclient)
+                .context("Failed to query branches")?;
+
+            println!("{}", String::from_utf8(raw_json).unwrap());
+            Ok(())
+        }
+
+        Some(OutputFormat::Text) => {
+            let branches: Vec<Branch> = api::paged(endpoint, api::Pagination::Limit(max as usize))
+                .query(&gitlabclient)
+                .context("Failed to query branches")?;
+
+            print_branches(
+                branches,
+                args.occurrences_of("no_headers") > 0,
+                args.occurrences_of("human_friendly") > 0,
+            );
+            Ok(())
+        }
+        _ => Err(anyhow!("Bad output format in config")),
+    }
+}
@@ -0,0 +1,227 @@
+mod create;
+mod delete;
+mod list;
+mod protect;
+mod prune;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::config;
+use crate::gitlab;
+use crate::subcommand;
+use crate::utils;
+use crate::utils::validator;
+
+#[derive(Debug, Deserialize)]
+pub struct Branch {
+    name: String,
+    merged: bool,
+    protected: bool,
+    default: bool,
+    commit: Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProtectedBranch {
+    name: String,
+    push_access_levels: Vec<Map<String, Value>>,
+    merge_access_levels: Vec<Map<String, Value>>,
+}
+
+const ACCESS_LEVELS: [&str; 4] = ["no_access", "developer", "maintainer", "admin"];
+
+const PROJECT_ID_HELP: &str = "Project ID to look for branch in. Defaults to attached Project ID.";
+
+/// This implements the `branch` command. It provides the ability to manage the branches of the
+/// attached project on the server, including their protection, and to tidy up local branches which
+/// are no longer needed.
+pub struct BranchCmd<'a> {
+    pub clap_cmd: clap::App<'a, 'a>,
+}
+
+impl subcommand::SubCommand for BranchCmd<'_> {
+    fn gen_clap_command(&self) -> clap::App {
+        let c = self.clap_cmd.clone();
+        c.about("Manages branches")
+            .setting(clap::AppSettings::ColoredHelp)
+            .setting(clap::AppSettings::VersionlessSubcommands)
+            .setting(clap::AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                clap::SubCommand::with_name("list")
+                    .about("Lists branches on the server")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("filter")
+                            .long("filter")
+                            .short("f")
+                            .help("Fetch branches whose names contain this")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .conflicts_with("protected")
+                    )
+                    .arg(
+                        clap::Arg::with_name("protected")
+                            .long("protected")
+                            .short("P")
+                            .help("Fetch protected branches and their access levels")
+                    )
+                    .arg(
+                        clap::Arg::with_name("max")
+                            .long("max")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .default_value("40")
+                            .help("Maximum records to return")
+                            .validator(validator::check_u32)
+                    )
+                    .arg(
+                        clap::Arg::with_name("no_headers")
+                            .long("no_headers")
+                            .help("Suppress header row on text output")
+                    )
+                    .arg(
+                        clap::Arg::with_name("human_friendly")
+                            .short("h")
+                            .help("Use human-friendly date-time strings")
+                    )
+                    .arg(utils::project_id_arg(PROJECT_ID_HELP))
+            )
+            .subcommand(
+                clap::SubCommand::with_name("create")
+                    .about("Creates a branch on the server")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("name")
+                            .help("Branch name")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                            .validator(validator::check_branch_name)
+                    )
+                    .arg(
+                        clap::Arg::with_name("ref")
+                            .long("ref")
+                            .short("r")
+                            .help("Branch name or commit SHA to create the branch from. Defaults to the default branch.")
+                            .takes_value(true)
+                            .empty_values(false)
+                    )
+                    .arg(utils::project_id_arg(PROJECT_ID_HELP))
+            )
+            .subcommand(
+                clap::SubCommand::with_name("delete")
+                    .about("Deletes branch(es) on the server")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("names")
+                            .help("Branch name(s)")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                            .multiple(true)
+                    )
+                    .arg(utils::project_id_arg(PROJECT_ID_HELP))
+            )
+            .subcommand(
+                clap::SubCommand::with_name("protect")
+                    .about("Protects branch(es) on the server")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("name")
+                            .help("Branch name, or wildcard such as `release/*`")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                    )
+                    .arg(
+                        clap::Arg::with_name("push")
+                            .long("push")
+                            .help("Access level allowed to push")
+                            .takes_value(true)
+                            .possible_values(&ACCESS_LEVELS)
+                    )
+                    .arg(
+                        clap::Arg::with_name("merge")
+                            .long("merge")
+                            .help("Access level allowed to merge")
+                            .takes_value(true)
+                            .possible_values(&ACCESS_LEVELS)
+                    )
+                    .arg(utils::project_id_arg(PROJECT_ID_HELP))
+                    .after_help(
+"Access levels not passed default to `maintainer`. A branch which is already protected must be \
+unprotected before it can be protected again with different access levels.",
+                    ),
+            )
+            .subcommand(
+                clap::SubCommand::with_name("unprotect")
+                    .about("Unprotects branch(es) on the server")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("name")
+                            .help("Branch name, or wildcard, as protected")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                    )
+                    .arg(utils::project_id_arg(PROJECT_ID_HELP))
+            )
+            .subcommand(
+                clap::SubCommand::with_name("prune")
+                    .about("Deletes local branches which are no longer needed")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("dry_run")
+                            .long("dry_run")
+                            .visible_alias("dry-run")
+                            .short("n")
+                            .help("Only list the branches which would be deleted")
+                    )
+                    .arg(
+                        clap::Arg::with_name("yes")
+                            .long("yes")
+                            .short("y")
+                            .help("Delete the branches without asking for confirmation")
+                            .conflicts_with("dry_run")
+                    )
+                    .arg(
+                        clap::Arg::with_name("force")
+                            .long("force")
+                            .short("f")
+                            .help("Also delete branches with commits which were never pushed")
+                    )
+                    .after_help(
+"A local branch is pruned if the merge requests raised from it have all been merged or closed, or \
+if the remote branch it tracks has been deleted. The remotes of the attached project, and of its \
+fork if set up with `git lab project fork`, are fetched first to find out which remote branches \
+remain. The current and default branches are never pruned.
+
+Branches are only deleted if their commits are safe elsewhere: the local branch must point at the \
+head of one of its merge requests, or at a commit on a remote branch. Other branches are listed as \
+kept, and are only deleted with `--force`.",
+                    ),
+            )
+    }
+
+    fn run(&self, config: config::Config, args: clap::ArgMatches) -> Result<()> {
+
+        trace!("Config: {:?}", config);
+        debug!("Args: {:#?}", args);
+
+        let gitlabclient = gitlab::new(&config).context("Could not create GitLab client connection.")?;
+
+        match args.subcommand() {
+            ("create", Some(a)) => create::create_branch_cmd(a.clone(), config, *gitlabclient)?,
+            ("delete", Some(a)) => delete::delete_branches_cmd(a.clone(), config, *gitlabclient)?,
+            ("list", Some(a)) => list::list_branches_cmd(a.clone(), config, *gitlabclient)?,
+            ("protect", Some(a)) => protect::protect_branch_cmd(a.clone(), config, *gitlabclient)?,
+            ("prune", Some(a)) => prune::prune_branches_cmd(a.clone(), config, *gitlabclient)?,
+            ("unprotect", Some(a)) => protect::unprotect_branch_cmd(a.clone(), config, *gitlabclient)?,
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use anyhow::{anyhow, Context, Result};
+
+use crate::config;
+use crate::config::OutputFormat;
+use crate::gitlab::converter::protected_access_level_from_str;
+use crate::gitlab::{api, Client, ProtectBranch, Query, UnprotectBranch};
+use crate::utils;
+
+pub fn protect_branch_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+
+    let mut p = ProtectBranch::builder();
+    p.project(project_id).name(args.value_of("name").unwrap());
+
+    if let Some(level) = args.value_of("push") {
+        p.push_access_level(protected_access_level_from_str(level)?);
+    }
+
+    if let Some(level) = args.value_of("merge") {
+        p.merge_access_level(protected_access_level_from_str(level)?);
+    }
+
+    let endpoint = p
+        .build()
+        .map_err(|e| anyhow!("Could not construct branch protection query.\n {}", e))?;
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    match config.format {
+        Some(OutputFormat::JSON) => {
+            let raw_json = api::raw(endpoint)
+                .query(&gitlabclient)
+                .context("Failed to protect branch")?;
+
+            println!("{}", String::from_utf8(raw_json).unwrap());
+            Ok(())
+        }
+
+        Some(OutputFormat::Text) => {
+            api::ignore(endpoint)
+                .query(&gitlabclient)
+                .context("Failed to protect branch")?;
+            Ok(())
+        }
+        _ => Err(anyhow!("Bad output format in config")),
+    }
+}
+
+pub fn unprotect_branch_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+
+    let endpoint = UnprotectBranch::builder()
+        .project(project_id)
+        .name(args.value_of("name").unwrap())
+        .build()
+        .map_err(|e| anyhow!("Could not construct branch protection query.\n {}", e))?;
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    api::ignore(endpoint)
+        .query(&gitlabclient)
+        .context("Failed to unprotect branch")?;
+
+    Ok(())
+}
@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context, Result};
+use dialoguer::Confirm;
+use git2::{BranchType, Oid, Repository};
+use graphql_client::GraphQLQuery;
+
+use crate::config;
+use crate::gitlab::Client;
+use crate::utils;
+
+use self::merge_requests_on_branches::MergeRequestState;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/merge_requests_on_branches.graphql",
+    response_derives = "Debug"
+)]
+struct MergeRequestsOnBranches;
+
+/// The number of branches to look up merge requests for in each query
+const BRANCHES_PER_QUERY: usize = 20;
+
+/// A merge request raised from a local branch
+#[derive(Debug)]
+struct BranchMr {
+    iid: String,
+    state: MergeRequestState,
+    head_sha: Option<String>,
+}
+
+/// Look up the merge requests raised from the given branches, by source branch
+fn get_branch_mrs(
+    project_path: &str,
+    branches: &[String],
+    gitlabclient: &Client,
+) -> Result<HashMap<String, Vec<BranchMr>>> {
+    let mut mrs: HashMap<String, Vec<BranchMr>> = HashMap::new();
+
+    for chunk in branches.chunks(BRANCHES_PER_QUERY) {
+        let mut after = None;
+
+        loop {
+            let query_body = MergeRequestsOnBranches::build_query(
+                merge_requests_on_branches::Variables {
+                    source_branches: Some(chunk.to_vec()),
+                    proj_path: project_path.to_string(),
+                    after: after.take(),
+                }
+            );
+
+            let page = gitlabclient.graphql::<MergeRequestsOnBranches>(&query_body)
+                .context("Failed to query merge requests on branches")?
+                .project
+                .and_then(|p| p.merge_requests);
+
+            let page = match page {
+                Some(p) => p,
+                None => break,
+            };
+
+            for mr in page.nodes.unwrap_or_default().into_iter().flatten() {
+                mrs.entry(mr.source_branch).or_default().push(BranchMr {
+                    iid: mr.iid,
+                    state: mr.state,
+                    head_sha: mr.diff_head_sha,
+                });
+            }
+
+            match Some(page.page_info).filter(|i| i.has_next_page).and_then(|i| i.end_cursor) {
+                Some(cursor) => after = Some(cursor),
+                None => break,
+            }
+        }
+    }
+
+    debug!("merge requests by branch: {:#?}", mrs);
+    Ok(mrs)
+}
+
+/// Why a branch can be pruned, if it can: because all the merge requests raised from it are merged
+/// or closed, or else because the remote branch it tracks has gone
+fn prune_reason(mrs: Option<&Vec<BranchMr>>, upstream_gone: bool) -> Option<String> {
+    let ids = |mrs: &[BranchMr]| mrs
+        .iter()
+        .map(|m| format!("!{}", m.iid))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    match mrs {
+        Some(m) if !m.is_empty() && m.iter().all(|m| m.state == MergeRequestState::merged || m.state == MergeRequestState::closed) => {
+            if m.iter().any(|m| m.state == MergeRequestState::merged) {
+                Some(format!("merged ({})", ids(m)))
+            } else {
+                Some(format!("closed ({})", ids(m)))
+            }
+        },
+        _ if upstream_gone => Some("remote branch deleted".to_string()),
+        _ => None,
+    }
+}
+
+/// Whether a branch's commits are safe elsewhere, because it points at the head of one of its merge
+/// requests or at a commit on a remote branch, so deleting it loses nothing
+fn is_pushed(repo: &Repository, tip: Oid, mrs: Option<&Vec<BranchMr>>, remote_heads: &[Oid]) -> bool {
+    let mr_heads = mrs
+        .into_iter()
+        .flatten()
+        .filter_map(|m| m.head_sha.as_deref().and_then(|s| Oid::from_str(s).ok()))
+        .collect::<Vec<Oid>>();
+
+    utils::contained_in_any(repo, tip, &mr_heads) || utils::contained_in_any(repo, tip, remote_heads)
+}
+
+pub fn prune_branches_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let repo_path = config.repo_path.as_ref()
+        .ok_or_else(|| anyhow!("Local repo not found. Are you in the correct directory?"))?;
+
+    let project_path = config.path_with_namespace.as_ref()
+        .ok_or_else(|| anyhow!("No project attached - try `git lab project attach`"))?;
+
+    debug!("args: {:#?}", args);
+
+    // bring the remote-tracking branches up to date, so those deleted on the server are gone
+    let mut remotes = vec![utils::get_gitlab_remote(&config)];
+    if let Some(f) = &config.fork_remote {
+        remotes.push(f.clone());
+    }
+    for remote in &remotes {
+        utils::run_git(&["fetch", "--prune", remote])?;
+    }
+
+    let repo = Repository::open(repo_path)
+        .context("Could not find local repo")?;
+    let repo_config = repo.config()?.snapshot()?;
+
+    let current = repo.head().ok().and_then(|h| h.shorthand().map(|s| s.to_string()));
+
+    let mut branches = Vec::new();
+    let mut gone = Vec::new();
+    for b in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = b?;
+        let name = match branch.name()? {
+            Some(n) => n.to_string(),
+            None => continue,
+        };
+
+        if Some(&name) == current.as_ref() || Some(&name) == config.defaultbranch.as_ref() {
+            continue;
+        }
+
+        let tip = match branch.get().target() {
+            Some(t) => t,
+            None => continue,
+        };
+
+        let tracks_remote = repo_config.get_str(&format!("branch.{}.merge", name)).is_ok();
+        if tracks_remote && branch.upstream().is_err() {
+            gone.push(name.clone());
+        }
+        branches.push((name, tip));
+    }
+
+    let remote_heads = repo.branches(Some(BranchType::Remote))?
+        .filter_map(|b| b.ok().and_then(|(b, _)| b.get().target()))
+        .collect::<Vec<Oid>>();
+
+    let names = branches.iter().map(|(b, _)| b.clone()).collect::<Vec<String>>();
+    let mrs = get_branch_mrs(project_path, &names, &gitlabclient)?;
+
+    let force = args.is_present("force");
+    let mut prunable = Vec::new();
+    let mut kept = Vec::new();
+    for (branch, tip) in branches {
+        if let Some(reason) = prune_reason(mrs.get(&branch), gone.contains(&branch)) {
+            if force || is_pushed(&repo, tip, mrs.get(&branch), &remote_heads) {
+                prunable.push((branch, reason));
+            } else {
+                kept.push((branch, format!("{}, kept (unpushed commits)", reason)));
+            }
+        }
+    }
+
+    if prunable.is_empty() && kept.is_empty() {
+        println!("No branches to prune");
+        return Ok(())
+    }
+
+    let width = prunable.iter().chain(kept.iter()).map(|(b, _)| b.len()).max().unwrap_or(0);
+    for (branch, reason) in prunable.iter().chain(kept.iter()) {
+        println!("  {:<width$}  {}", branch, reason, width = width);
+    }
+
+    if !kept.is_empty() {
+        println!("Branches with unpushed commits are kept - delete them too with `--force`");
+    }
+
+    if prunable.is_empty() || args.is_present("dry_run") {
+        return Ok(())
+    }
+
+    if !args.is_present("yes") && !Confirm::new()
+        .with_prompt(format!("Delete {} branch(es)?", prunable.len()))
+        .default(false)
+        .interact()?
+    {
+        return Ok(())
+    }
+
+    for (name, _) in prunable {
+        repo.find_branch(&name, BranchType::Local)?
+            .delete()
+            .with_context(|| format!("Failed to delete branch {}", name))?;
+        println!("Deleted branch {}", name);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod branch_prune_unit_tests {
+    use assert_fs::TempDir;
+    use git2::Signature;
+    use rstest::*;
+
+    use super::*;
+
+    fn mrs(states: Vec<MergeRequestState>) -> Vec<BranchMr> {
+        states
+            .into_iter()
+            .enumerate()
+            .map(|(i, state)| BranchMr { iid: (i + 1).to_string(), state, head_sha: None })
+            .collect()
+    }
+
+    #[rstest(
+        states, upstream_gone, expected,
+        case(vec![MergeRequestState::merged], false, Some("merged (!1)")),
+        case(vec![MergeRequestState::closed, MergeRequestState::merged], false, Some("merged (!1, !2)")),
+        case(vec![MergeRequestState::closed], true, Some("closed (!1)")),
+        case(vec![MergeRequestState::closed, MergeRequestState::opened], false, None),
+        case(vec![MergeRequestState::locked], true, Some("remote branch deleted")),
+        case(vec![], true, Some("remote branch deleted")),
+        case(vec![], false, None),
+    )]
+    fn test_prune_reason(states: Vec<MergeRequestState>, upstream_gone: bool, expected: Option<&str>) {
+        let m = mrs(states);
+        assert_eq!(prune_reason(Some(&m), upstream_gone).as_deref(), expected);
+    }
+
+    #[test]
+    fn test_prune_reason_no_mrs() {
+        assert_eq!(prune_reason(None, false), None);
+    }
+
+    #[test]
+    fn test_is_pushed() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let sig = Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+
+        let first = repo.commit(None, &sig, &sig, "first", &tree, &[]).unwrap();
+        let parent = repo.find_commit(first).unwrap();
+        let second = repo.commit(None, &sig, &sig, "second", &tree, &[&parent]).unwrap();
+
+        let mut m = mrs(vec![MergeRequestState::closed]);
+        assert!(!is_pushed(&repo, second, Some(&m), &[first]));
+        assert!(is_pushed(&repo, first, Some(&m), &[second]));
+
+        m[0].head_sha = Some(second.to_string());
+        assert!(is_pushed(&repo, second, Some(&m), &[]));
+        assert!(!is_pushed(&repo, second, None, &[]));
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::{anyhow, Context, Result};
+use git2::Repository;
+use serde::Deserialize;
+
+use crate::cmds::issue::generate_basic_issue_builder;
+use crate::cmds::mr::create::{create_remote_branch, slug_and_prefix};
+use crate::config;
+use crate::gitlab::Issue as GLIssue;
+use crate::gitlab::{Client, Query};
+use crate::utils;
+
+pub fn branch_issue_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let repo_path = config.repo_path.as_ref()
+        .ok_or_else(|| anyhow!("Local repo not found. Are you in the correct directory?"))?;
+
+    let defaultbranch = config.defaultbranch.as_ref()
+        .ok_or_else(|| anyhow!("Could not determine default remote branch - try `git lab project refresh`"))?;
+
+    #[derive(Deserialize, Debug)]
+    struct Issue { iid: u64, title: String, state: String }
+
+    let mut i = GLIssue::builder();
+    let endpoint = generate_basic_issue_builder(&args, "id", &config, &mut i)?;
+    let issue: Issue = endpoint
+        .query(&gitlabclient)
+        .context("Failed to find issue")?;
+
+    if issue.state == "closed" {
+        return Err(anyhow!("Issue #{} is closed.", issue.iid))
+    }
+
+    let branch = match args.value_of("name") {
+        Some(n) => n.to_string(),
+        None => slug_and_prefix(issue.iid, &issue.title),
+    };
+
+    // work starts from the attached project's default branch, but the branch itself belongs in the
+    // fork, if one has been set up with `project fork`
+    let base_remote = utils::get_gitlab_remote(&config);
+    let (remote, branch_project_id) = match (&config.fork_remote, config.fork_project_id) {
+        (Some(r), Some(p)) => (r.clone(), p),
+        _ => (base_remote.clone(), utils::get_proj_from_arg_or_conf(&args, &config)?),
+    };
+
+    debug!("args: {:#?}", args);
+    debug!("branch: {}, remote: {}, project: {}", branch, remote, branch_project_id);
+
+    utils::run_git(&["fetch", &base_remote, defaultbranch])?;
+
+    if args.is_present("remote_branch") {
+        create_remote_branch(branch_project_id, defaultbranch, &branch, &gitlabclient)?;
+        utils::run_git(&["fetch", &remote, &branch])?;
+        utils::run_git(&["checkout", "-b", &branch, "--track", &format!("{}/{}", remote, branch)])?;
+    } else {
+        utils::run_git(&["checkout", "-b", &branch, "--no-track", &format!("{}/{}", base_remote, defaultbranch)])?;
+
+        // track the branch-to-be on the remote, so that a plain `git push` creates it
+        let repo = Repository::open(repo_path)
+            .context("Could not find local repo")?;
+        let mut repo_config = repo.config()?;
+        repo_config.set_str(&format!("branch.{}.remote", branch), &remote)?;
+        repo_config.set_str(&format!("branch.{}.merge", branch), &format!("refs/heads/{}", branch))?;
+    }
+
+    Ok(())
+}
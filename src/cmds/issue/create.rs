@@ -4,6 +4,7 @@ use clap::value_t_or_exit;
 use dialoguer::{Confirm, Input, Editor, MultiSelect};
 
 use crate::cmds::issue::Issue;
+use crate::cmds::templates::{get_template, TemplateType};
 use crate::config;
 use crate::config::OutputFormat;
 use crate::gitlab::{api, Client, CreateIssue, CreateIssueBuilder, Query};
@@ -13,18 +14,24 @@ use crate::utils::validator;
 pub fn generate_issue_builder<'a>(
     args: &'a clap::ArgMatches,
     config: &'a config::Config,
+    template: Option<String>,
     i: &'a mut CreateIssueBuilder<'a>,
 ) -> Result<CreateIssue<'a>> {
 
     let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
     i.project(project_id);
 
+    if let Some(t) = template {
+        i.description(t);
+    }
+
     for arg in &args.args {
         let (key, _) = arg;
         match *key {
             // straight string arguments
             "title" => i.title(args.value_of("title").unwrap()),
             "description" => i.description(args.value_of("description").unwrap()),
+            "template" => continue,
 
             // u64 arguments
             "project_id" => i.project(value_t_or_exit!(args, "project_id", u64)),
@@ -59,6 +66,7 @@ pub fn generate_issue_builder<'a>(
 fn interactive_issue_builder<'a>(
     args: &'a clap::ArgMatches,
     config: &'a config::Config,
+    template: Option<String>,
     i: &'a mut CreateIssueBuilder<'a>,
 ) -> Result<CreateIssue<'a>> {
 
@@ -79,8 +87,8 @@ fn interactive_issue_builder<'a>(
         Editor::new()
             .extension(".md")
             .require_save(true)
-            .edit("<!-- insert issue description here - save and quit when done -->")?
-    } else { None };
+            .edit(template.as_deref().unwrap_or("<!-- insert issue description here - save and quit when done -->"))?
+    } else { template };
 
     if let Some(desc) = description {
         i.description(desc);
@@ -179,10 +187,18 @@ pub fn create_issue_cmd(args: clap::ArgMatches, config: config::Config, gitlabcl
 
     let interactive = !args.is_present("title");
 
+    // only pick a template for non-interactive use if one is asked for
+    let template = if interactive || args.is_present("template") {
+        let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+        get_template(&args, TemplateType::Issue, project_id, &config, &gitlabclient)?
+    } else {
+        None
+    };
+
     let endpoint = if !interactive {
-        generate_issue_builder(&args, &config, &mut i)?
+        generate_issue_builder(&args, &config, template, &mut i)?
     } else {
-        interactive_issue_builder(&args, &config, &mut i)?
+        interactive_issue_builder(&args, &config, template, &mut i)?
     };
 
     debug!("args: {:#?}", args);
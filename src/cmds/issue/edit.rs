@@ -0,0 +1,278 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::NaiveDate;
+use clap::value_t_or_exit;
+use dialoguer::{Confirm, Input, Editor, MultiSelect};
+
+use crate::cmds::issue::{generate_basic_issue_builder, Issue};
+use crate::config;
+use crate::config::OutputFormat;
+use crate::gitlab::Issue as GLIssue;
+use crate::gitlab::{api, Client, Query, UpdateIssue, UpdateIssueBuilder};
+use crate::utils;
+use crate::utils::validator;
+
+pub fn generate_update_issue_builder<'a>(
+    args: &'a clap::ArgMatches,
+    i: &'a mut UpdateIssueBuilder<'a>,
+) -> Result<UpdateIssue<'a>> {
+
+    for arg in &args.args {
+        let (key, _) = arg;
+        match *key {
+            // straight string arguments
+            "title" => i.title(args.value_of("title").unwrap()),
+            "description" => i.description(args.value_of("description").unwrap()),
+
+            // u64 arguments
+            "milestone_id" => i.milestone_id(value_t_or_exit!(args, "milestone_id", u64)),
+            "weight" => i.weight(value_t_or_exit!(args, "weight", u64)),
+
+            // boolean flags
+            "confidential" => i.confidential(true),
+            "not_confidential" => i.confidential(false),
+
+            // date flags
+            "due_date" => i.due_date(
+                NaiveDate::parse_from_str(args.value_of("due_date").unwrap(), "%Y-%m-%d")
+                .unwrap()
+                ),
+
+            // list parameters
+            "add_labels" => i.add_labels(args.values_of("add_labels").unwrap()),
+            "remove_labels" => i.remove_labels(args.values_of("remove_labels").unwrap()),
+
+            // identifying arguments
+            "id" => i,
+            "project_id" => i,
+            "interactive" => i,
+            "edits" => i,
+            "confidentiality" => i,
+
+            _ => unreachable!(),
+        };
+    }
+
+    i.build()
+        .map_err(|e| anyhow!("Could not construct issue edit to send to server.\n {}",e))
+}
+
+/// Prompt for each editable field, starting from the issue's current values
+fn interactive_update_issue_builder<'a>(
+    issue: &Issue,
+    config: &'a config::Config,
+    i: &'a mut UpdateIssueBuilder<'a>,
+) -> Result<UpdateIssue<'a>> {
+
+    let title = Input::<String>::new()
+        .with_prompt("Title")
+        .allow_empty(false)
+        .with_initial_text(issue.title.clone())
+        .interact()?;
+    if title != issue.title {
+        i.title(title);
+    }
+
+    if Confirm::new()
+        .with_prompt("Edit issue description?")
+        .default(true)
+        .show_default(true)
+        .interact()?
+    {
+        let current = issue.description.clone().unwrap_or_default();
+        if let Some(desc) = Editor::new()
+            .extension(".md")
+            .require_save(true)
+            .edit(&current)?
+        {
+            i.description(desc);
+        }
+    }
+
+    #[allow(clippy::redundant_closure)]  // below closure doesn't work unless called as shown below
+    let weight = Input::<String>::new()
+        .with_prompt("Weight")
+        .allow_empty(true)
+        .with_initial_text(issue.weight.map(|w| w.to_string()).unwrap_or_default())
+        .validate_with(|d: &str| validator::check_u32_or_empty(d))
+        .interact()?;
+    if weight.is_empty() {
+        if issue.weight.is_some() {
+            i.clear_weight(true);
+        }
+    } else if weight.parse::<u64>().ok() != issue.weight {
+        i.weight(
+            weight.parse::<u64>()
+            .unwrap()
+        );
+    }
+
+    let confidential = Input::<bool>::new()
+        .with_prompt("Confidential")
+        .default(issue.confidential)
+        .interact()?;
+    if confidential != issue.confidential {
+        i.confidential(confidential);
+    }
+
+    let current_milestone = issue.milestone.as_ref().and_then(|m| m["id"].as_u64());
+
+    #[allow(clippy::redundant_closure)]  // below closure doesn't work unless called as shown below
+    let milestone = Input::<String>::new()
+        .with_prompt("Milestone ID")
+        .allow_empty(true)
+        .with_initial_text(current_milestone.map(|m| m.to_string()).unwrap_or_default())
+        .validate_with(|d: &str| validator::check_u32_or_empty(d))
+        .interact()?;
+    match milestone.parse::<u64>().ok() {
+        // zero removes the milestone
+        None if current_milestone.is_some() => {
+            i.milestone_id(0);
+        },
+        Some(m) if Some(m) != current_milestone => {
+            i.milestone_id(m);
+        },
+        _ => (),
+    }
+
+    #[allow(clippy::redundant_closure)]  // below closure doesn't work unless called as shown below
+    let due_date = Input::<String>::new()
+        .with_prompt("Due date [YYYY-MM-DD]")
+        .allow_empty(true)
+        .with_initial_text(issue.due_date.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default())
+        .validate_with(|d: &str| validator::check_yyyy_mm_dd_or_empty(d))
+        .interact()?;
+    if due_date.is_empty() {
+        if issue.due_date.is_some() {
+            i.clear_due_date(true);
+        }
+    } else {
+        let due_date = NaiveDate::parse_from_str(&due_date, "%Y-%m-%d").unwrap();
+        if Some(due_date) != issue.due_date {
+            i.due_date(due_date);
+        }
+    }
+
+    // offer the cached project labels, plus any on the issue that aren't cached, with the
+    // issue's current labels checked
+    let mut labels = config.labels.clone();
+    labels.extend(issue.labels.iter().filter(|l| !config.labels.contains(l)).cloned());
+
+    if !labels.is_empty() {
+        let items = labels
+            .iter()
+            .map(|l| (l.as_str(), issue.labels.contains(l)))
+            .collect::<Vec<(&str, bool)>>();
+
+        let selected = MultiSelect::new()
+            .with_prompt("Label(s)")
+            .items_checked(&items[..])
+            .interact()?;
+
+        debug!("labels: {:#?}", selected);
+
+        let selected = selected
+            .iter()
+            .map(|x| labels[*x].clone())
+            .collect::<Vec<String>>();
+
+        i.add_labels(selected.iter().filter(|l| !issue.labels.contains(l)).cloned());
+        i.remove_labels(issue.labels.iter().filter(|l| !selected.contains(l)).cloned());
+    }
+
+    i.build()
+        .map_err(|e| anyhow!("Could not construct issue edit to send to server.\n {}",e))
+}
+
+pub fn edit_issue_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+    let issue_id = value_t_or_exit!(args, "id", u64);
+
+    let mut i = UpdateIssue::builder();
+    i.project(project_id).issue(issue_id);
+
+    let interactive = args.is_present("interactive");
+
+    let endpoint = if !interactive {
+        generate_update_issue_builder(&args, &mut i)?
+    } else {
+        let mut ib = GLIssue::builder();
+        let current_endpoint = generate_basic_issue_builder(&args, "id", &config, &mut ib)?;
+        let issue: Issue = current_endpoint
+            .query(&gitlabclient)
+            .context("Failed to find issue")?;
+
+        interactive_update_issue_builder(&issue, &config, &mut i)?
+    };
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    match (&config.format, interactive) {
+
+        (_, true) | (Some(OutputFormat::Text), _) => {
+            let issue: Issue = endpoint
+                .query(&gitlabclient)
+                .context("Failed to update issue")?;
+
+            println!("Issue URL: {}", issue.web_url);
+            Ok(())
+        },
+
+        (Some(OutputFormat::JSON), _) => {
+            let raw_json  = api::raw(endpoint)
+                .query(&gitlabclient)
+                .context("Failed to update issue")?;
+
+            println!("{}", String::from_utf8(raw_json).unwrap());
+            Ok(())
+        },
+
+        (None, _) => Err(anyhow!("Bad output format in config")),
+    }
+}
+
+#[cfg(test)]
+mod issue_edit_unit_tests {
+    use gitlab::api::Endpoint;
+
+    use crate::cmds::issue;
+    use crate::subcommand::SubCommand;
+    use clap::SubCommand as ClapSubCommand;
+
+    use super::*;
+
+    #[test]
+    fn test_generate_update_issue_builder() {
+        // GIVEN
+        let mut i = UpdateIssue::builder();
+        i.project(1).issue(5);
+
+        let i_cmd = issue::IssueCmd {
+            clap_cmd: ClapSubCommand::with_name("issue"),
+        };
+
+        let args = i_cmd.gen_clap_command().get_matches_from(vec![
+            "issue",
+            "edit",
+            "5",
+            "--title",
+            "New title",
+            "--weight",
+            "3",
+            "--not_confidential",
+            "--add_labels",
+            "bug,ui",
+        ]);
+        let matches = args.subcommand_matches("edit");
+
+        // WHEN
+        let endpoint = generate_update_issue_builder(matches.unwrap(), &mut i).unwrap();
+
+        // THEN
+        let (_, body) = endpoint.body().unwrap().unwrap();
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            "title=New+title&confidential=false&weight=3&add_labels=bug%2Cui"
+        );
+    }
+}
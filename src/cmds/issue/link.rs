@@ -0,0 +1,127 @@
+use anyhow::{anyhow, Context, Result};
+use clap::value_t_or_exit;
+use serde_json::{Map, Value};
+
+use crate::cmds::issue::LinkedIssue;
+use crate::config;
+use crate::config::OutputFormat;
+use crate::gitlab::converter::issue_link_type_from_str;
+use crate::gitlab::Project as GLProject;
+use crate::gitlab::{api, Client, CreateIssueLink, DeleteIssueLink, IssueLinks, Query};
+use crate::utils;
+use crate::utils::IssueReference;
+
+/// Fetch the issues linked to the given one
+pub fn get_issue_links(project_id: u64, issue_id: u64, gitlabclient: &Client) -> Result<Vec<LinkedIssue>> {
+    let endpoint = IssueLinks::builder()
+        .project(project_id)
+        .issue(issue_id)
+        .build()
+        .map_err(|e| anyhow!("Could not construct issue links query.\n {}", e))?;
+
+    endpoint
+        .query(gitlabclient)
+        .context("Failed to find linked issues")
+}
+
+/// Resolve the project of an issue reference to its ID, defaulting to the given project
+fn get_reference_project_id(reference: &IssueReference, project_id: u64, gitlabclient: &Client) -> Result<u64> {
+    match &reference.project {
+        Some(path) => {
+            let project: Map<String, Value> = GLProject::builder()
+                .project(path.as_str())
+                .build()
+                .map_err(|e| anyhow!("Could not construct project query.\n {}", e))?
+                .query(gitlabclient)
+                .with_context(|| format!("Failed to find project {}", path))?;
+
+            project["id"].as_u64().context("Project has no ID")
+        },
+        None => Ok(project_id),
+    }
+}
+
+pub fn link_issue_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+    let issue_id = value_t_or_exit!(args, "id", u64);
+    let other: IssueReference = args.value_of("other").unwrap().parse()?;
+
+    let mut l = CreateIssueLink::builder();
+    l.project(project_id)
+        .issue(issue_id)
+        .target_issue(other.iid)
+        .link_type(issue_link_type_from_str(args.value_of("type").unwrap())?);
+
+    match &other.project {
+        Some(path) => l.target_project(path.as_str()),
+        None => l.target_project(project_id),
+    };
+
+    let endpoint = l
+        .build()
+        .map_err(|e| anyhow!("Could not construct issue link query.\n {}", e))?;
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    match config.format {
+        Some(OutputFormat::JSON) => {
+            let raw_json = api::raw(endpoint)
+                .query(&gitlabclient)
+                .context("Failed to link issues")?;
+
+            println!("{}", String::from_utf8(raw_json).unwrap());
+            Ok(())
+        }
+
+        Some(OutputFormat::Text) => {
+            api::ignore(endpoint)
+                .query(&gitlabclient)
+                .context("Failed to link issues")?;
+            Ok(())
+        }
+        _ => Err(anyhow!("Bad output format in config")),
+    }
+}
+
+pub fn unlink_issue_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+    let issue_id = value_t_or_exit!(args, "id", u64);
+    let other: IssueReference = args.value_of("other").unwrap().parse()?;
+    let other_project_id = get_reference_project_id(&other, project_id, &gitlabclient)?;
+
+    // links are removed by their own ID, so find it from those of the issue
+    let link = get_issue_links(project_id, issue_id, &gitlabclient)?
+        .into_iter()
+        .find(|l| l.project_id == other_project_id && l.iid == other.iid)
+        .ok_or_else(|| anyhow!("Issue {} is not linked to {}", issue_id, args.value_of("other").unwrap()))?;
+
+    let endpoint = DeleteIssueLink::builder()
+        .project(project_id)
+        .issue(issue_id)
+        .issue_link(link.issue_link_id)
+        .build()
+        .map_err(|e| anyhow!("Could not construct issue link query.\n {}", e))?;
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    match config.format {
+        Some(OutputFormat::JSON) => {
+            let raw_json = api::raw(endpoint)
+                .query(&gitlabclient)
+                .context("Failed to unlink issues")?;
+
+            println!("{}", String::from_utf8(raw_json).unwrap());
+            Ok(())
+        }
+
+        Some(OutputFormat::Text) => {
+            api::ignore(endpoint)
+                .query(&gitlabclient)
+                .context("Failed to unlink issues")?;
+            Ok(())
+        }
+        _ => Err(anyhow!("Bad output format in config")),
+    }
+}
@@ -62,7 +62,7 @@ pub fn generate_issues_builder<'a>(
         .map_err(|e| anyhow!("Could not construct issues query.\n {}", e))
 }
 
-fn print_issues(issues: Vec<Issue>, fields: Vec<String>, no_headers: bool, human: bool) {
+pub fn print_issues(issues: Vec<Issue>, fields: Vec<String>, no_headers: bool, human: bool) {
     let mut table = Table::new();
 
     table
@@ -1,11 +1,16 @@
+mod branch;
 mod create;
-mod list;
+mod edit;
+mod link;
+pub mod list;
 mod open;
 mod show;
 mod quick_edit;
+mod related;
 
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc, NaiveDate};
+use clap::ArgGroup;
 use serde::Deserialize;
 use serde_json::{Map, Value};
 
@@ -13,6 +18,9 @@ use crate::config;
 use crate::gitlab::Issue as GLIssue;
 use crate::gitlab::IssueBuilder;
 use crate::gitlab;
+use crate::gitlab::TimeTrackable;
+use crate::cmds::templates::template_arg;
+use crate::cmds::time_tracking;
 use crate::subcommand;
 use crate::utils::validator;
 use crate::utils;
@@ -39,7 +47,7 @@ pub struct Issue {
     closed_at: Option<DateTime<Utc>>,
     closed_by: Option<Map<String, Value>>,
     labels: Vec<String>,
-    milestone: Option<String>,
+    milestone: Option<Map<String, Value>>,
     author: Map<String, Value>,
     // FIXME: assignees is there all the time, remove the Option at some point
     assignees: Option<Vec<Map<String, Value>>>,
@@ -59,6 +67,37 @@ pub struct Issue {
     subscribed: Option<bool>,
 }
 
+/// An issue linked to another, as returned when listing the other's links
+#[derive(Debug, Deserialize)]
+pub struct LinkedIssue {
+    iid: u64,
+    project_id: u64,
+    title: String,
+    state: String,
+    references: Option<Map<String, Value>>,
+    issue_link_id: u64,
+    link_type: String,
+}
+
+/// A merge request which mentions, or will close, an issue
+#[derive(Debug, Deserialize)]
+pub struct RelatedMergeRequest {
+    iid: u64,
+    project_id: u64,
+    source_project_id: u64,
+    target_project_id: u64,
+    title: String,
+    state: String,
+    author: Map<String, Value>,
+    source_branch: String,
+    references: Option<Map<String, Value>>,
+    #[serde(alias = "pipeline")]
+    head_pipeline: Option<Map<String, Value>>,
+    /// Whether merging will close the issue, which GitLab reports separately
+    #[serde(skip)]
+    closes_issue: bool,
+}
+
 pub fn generate_basic_issue_builder<'a>(
     args: &'a clap::ArgMatches,
     issue_arg_name: &str,
@@ -344,6 +383,170 @@ form: `y, M, d, h, m, s`."
                             .validator(validator::check_u64)
                     )
             )
+            .subcommand(
+                clap::SubCommand::with_name("branch")
+                    .about("Creates and checks out a branch to work on an issue")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("id")
+                            .help("Issue ID")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("name")
+                            .long("name")
+                            .short("n")
+                            .help("Branch name. Defaults to the issue ID followed by its slugified title")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .validator(validator::check_branch_name)
+                    )
+                    .arg(
+                        clap::Arg::with_name("remote_branch")
+                            .long("remote_branch")
+                            .short("r")
+                            .help("Also creates the branch on the GitLab server")
+                    )
+                    .arg(
+                        clap::Arg::with_name("project_id")
+                            .short("p")
+                            .long("project_id")
+                            .help("Project ID to look for issue in. Defaults to attached Project ID.")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .validator(validator::check_u64)
+                    )
+                    .after_help(
+"The branch is created from the attached project's default branch, named as `mr create` would \
+name it, e.g., `12-fix-the-widget` for issue 12. It is set up to track a branch of the same name on \
+the attached project's remote (or the fork's, if one has been set up with `project fork`), which \
+is created there immediately with `--remote_branch`, or else by the first `git push`.
+
+No merge request is created - use `git lab mr create --issue_id` when ready."
+                    ),
+            )
+            .subcommand(
+                clap::SubCommand::with_name("checkout")
+                    .about("Checks out the merge request for an issue locally")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .visible_alias("co")
+                    .arg(
+                        clap::Arg::with_name("id")
+                            .help("Issue ID")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("mr_id")
+                            .long("mr")
+                            .short("m")
+                            .help("Merge request to check out, if the issue has more than one")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("project_id")
+                            .short("p")
+                            .long("project_id")
+                            .help("Project ID to look for issue in. Defaults to attached Project ID.")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("remote")
+                            .short("r")
+                            .long("remote")
+                            .help("Git remote to fetch the merge request from. Defaults to the remote of the project it targets")
+                            .empty_values(false)
+                            .takes_value(true)
+                    )
+                    .after_help(
+"This checks out the source branch of the open merge request in the project which is related to, \
+or will close, the issue. If there is more than one, the one which will close the issue is chosen, \
+and if that is still ambiguous you are asked which to check out."
+                    ),
+            )
+            .subcommand(
+                clap::SubCommand::with_name("link")
+                    .about("Links an issue to another")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("id")
+                            .help("Issue ID")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("other")
+                            .help("Issue to link to, e.g., 12, #12 or group/project#12")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                            .validator(validator::check_issue_reference)
+                    )
+                    .arg(
+                        clap::Arg::with_name("type")
+                            .long("type")
+                            .short("t")
+                            .help("Type of link")
+                            .takes_value(true)
+                            .possible_values(&["relates_to", "blocks", "is_blocked_by"])
+                            .default_value("relates_to")
+                    )
+                    .arg(
+                        clap::Arg::with_name("project_id")
+                            .short("p")
+                            .long("project_id")
+                            .help("Project ID to look for issue in. Defaults to attached Project ID.")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .validator(validator::check_u64)
+                    )
+                    .after_help(
+"The other issue is looked for in the same project unless it is given with the full path of its \
+project, e.g., `group/project#12`. Note that `blocks` and `is_blocked_by` links require GitLab \
+Starter or higher."
+                    ),
+            )
+            .subcommand(
+                clap::SubCommand::with_name("unlink")
+                    .about("Removes the link between an issue and another")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("id")
+                            .help("Issue ID")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("other")
+                            .help("Linked issue, e.g., 12, #12 or group/project#12")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                            .validator(validator::check_issue_reference)
+                    )
+                    .arg(
+                        clap::Arg::with_name("project_id")
+                            .short("p")
+                            .long("project_id")
+                            .help("Project ID to look for issue in. Defaults to attached Project ID.")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .validator(validator::check_u64)
+                    )
+            )
             .subcommand(
                 clap::SubCommand::with_name("unlock")
                     .about("Unlocks an issue")
@@ -432,6 +635,7 @@ form: `y, M, d, h, m, s`."
                             .validator(validator::check_u64)
                     )
             )
+            .subcommand(time_tracking::gen_time_clap_command(TimeTrackable::Issue))
             .subcommand(
                 clap::SubCommand::with_name("show")
                     .about("Shows issue information in the terminal")
@@ -489,6 +693,135 @@ environment variable to determine which browser to use. If this is not set, on L
 try `xdg-open(1)`.",
                     ),
             )
+            .subcommand(
+                clap::SubCommand::with_name("edit")
+                    .about("Edits a GitLab issue")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .setting(clap::AppSettings::DeriveDisplayOrder)
+                    // clap rejects arguments in a group alongside one conflicting with another of
+                    // its members, so conflicts are set between groups instead
+                    .group(ArgGroup::with_name("edits")
+                        .multiple(true)
+                        .conflicts_with("interactive")
+                        )
+                    .group(ArgGroup::with_name("confidentiality"))
+                    .arg(
+                        clap::Arg::with_name("id")
+                            .help("Issue ID to edit")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("project_id")
+                            .long("project_id")
+                            .short("p")
+                            .help("Project id - defaults to the attached project")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("title")
+                            .long("title")
+                            .short("t")
+                            .help("Sets the issue title")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .group("edits")
+                    )
+                    .arg(
+                        clap::Arg::with_name("description")
+                            .long("desc")
+                            .short("d")
+                            .help("Sets the issue description")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .group("edits")
+                    )
+                    .arg(
+                        clap::Arg::with_name("confidential")
+                        .long("confidential")
+                        .short("c")
+                        .help("Sets the issue to be confidential")
+                        .groups(&["edits", "confidentiality"])
+                    )
+                    .arg(
+                        clap::Arg::with_name("not_confidential")
+                        .long("not_confidential")
+                        .short("C")
+                        .help("Sets the issue to be non-confidential")
+                        .groups(&["edits", "confidentiality"])
+                    )
+                    .arg(
+                        clap::Arg::with_name("milestone_id")
+                            .long("milestone_id")
+                            .short("m")
+                            .takes_value(true)
+                            .help("Associates the issue to a milestone. 0 removes the milestone")
+                            .empty_values(false)
+                            .validator(validator::check_u32)
+                            .group("edits")
+                    )
+                    .arg(
+                        clap::Arg::with_name("due_date")
+                            .long("due_date")
+                            .short("u")
+                            .takes_value(true)
+                            .help("Due date in format YYYY-MM-DD")
+                            .empty_values(false)
+                            .validator(validator::check_yyyy_mm_dd)
+                            .group("edits")
+                    )
+                    .arg(
+                        clap::Arg::with_name("weight")
+                            .long("weight")
+                            .short("w")
+                            .takes_value(true)
+                            .help("Sets the weight of the issue")
+                            .empty_values(false)
+                            .validator(validator::check_u32)
+                            .group("edits")
+                    )
+                    .arg(
+                        clap::Arg::with_name("add_labels")
+                            .long("add_labels")
+                            .short("l")
+                            .help("Adds label(s) to the issue")
+                            .takes_value(true)
+                            .multiple(true)
+                            .empty_values(false)
+                            .require_delimiter(true)
+                            .group("edits")
+                    )
+                    .arg(
+                        clap::Arg::with_name("remove_labels")
+                            .long("remove_labels")
+                            .short("L")
+                            .help("Removes label(s) from the issue")
+                            .takes_value(true)
+                            .multiple(true)
+                            .empty_values(false)
+                            .require_delimiter(true)
+                            .group("edits")
+                    )
+                    .arg(
+                        clap::Arg::with_name("interactive")
+                            .long("interactive")
+                            .short("i")
+                            .help("Prompts for each field, starting from its current value")
+                            .required_unless("edits")
+                    )
+                    .after_help(
+"Only the fields passed are changed. Labels are added and removed individually, leaving any others \
+on the issue in place.\
+\n
+With `--interactive` the user is prompted to edit each field in turn, starting from the issue's \
+current values. The description is opened in $EDITOR, and clearing the weight or due date removes \
+it.",
+                    ),
+            )
             .subcommand(
                 clap::SubCommand::with_name("create")
                     .about("Creates a GitLab issue")
@@ -517,6 +850,10 @@ try `xdg-open(1)`.",
                             .empty_values(false)
                             .takes_value(true)
                     )
+                    .arg(
+                        template_arg()
+                            .conflicts_with("description")
+                    )
                     .arg(
                         clap::Arg::with_name("confidential")
                         .long("confidential")
@@ -571,7 +908,12 @@ try `xdg-open(1)`.",
                             .require_delimiter(true)
                     )
                     .after_help(
-"If the title is is omitted then the user will be prompted for issue parameters interactively",
+"If the title is is omitted then the user will be prompted for issue parameters interactively. \
+\
+The description then starts from a template in the project's .gitlab/issue_templates directory: the \
+one passed with `--template`, otherwise the project's default template (set in its settings, or \
+named Default.md), otherwise one chosen from those available. When the title is passed, a template \
+is only used if it is passed with `--template`.",
                     ),
             )
     }
@@ -584,7 +926,12 @@ try `xdg-open(1)`.",
         let gitlabclient = gitlab::new(&config).context("Could not create GitLab client connection.")?;
 
         match args.subcommand() {
+            ("branch", Some(a)) => branch::branch_issue_cmd(a.clone(), config, *gitlabclient)?,
+            ("checkout", Some(a)) => related::checkout_issue_cmd(a.clone(), config, *gitlabclient)?,
             ("create", Some(a)) => create::create_issue_cmd(a.clone(), config, *gitlabclient)?,
+            ("edit", Some(a)) => edit::edit_issue_cmd(a.clone(), config, *gitlabclient)?,
+            ("link", Some(a)) => link::link_issue_cmd(a.clone(), config, *gitlabclient)?,
+            ("unlink", Some(a)) => link::unlink_issue_cmd(a.clone(), config, *gitlabclient)?,
             ("open", Some(a)) => open::open_issue_cmd(a.clone(), config, *gitlabclient)?,
             ("show", Some(a)) => show::show_issue_cmd(a.clone(), config, *gitlabclient)?,
             ("list", Some(a)) => list::list_issues_cmd(a.clone(), config, *gitlabclient)?,
@@ -593,6 +940,7 @@ try `xdg-open(1)`.",
             ("reopen", Some(a)) => quick_edit::quick_edit_issue_cmd(a.clone(), ShortCmd::Reopen, config, *gitlabclient)?,
             ("lock", Some(a)) => quick_edit::quick_edit_issue_cmd(a.clone(), ShortCmd::Lock, config, *gitlabclient)?,
             ("unlock", Some(a)) => quick_edit::quick_edit_issue_cmd(a.clone(), ShortCmd::Unlock, config, *gitlabclient)?,
+            ("time", Some(a)) => time_tracking::time_cmd(a.clone(), TimeTrackable::Issue, config, *gitlabclient)?,
             _ => unreachable!(),
         }
 
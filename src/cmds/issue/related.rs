@@ -0,0 +1,115 @@
+use anyhow::{anyhow, Context, Result};
+use clap::value_t_or_exit;
+use dialoguer::Select;
+
+use crate::cmds::issue::RelatedMergeRequest;
+use crate::cmds::mr::{checkout_mr, CheckoutOptions};
+use crate::config;
+use crate::gitlab::{api, Client, MergeRequestsClosingIssue, Query, RelatedMergeRequests};
+use crate::utils;
+
+/// Fetch the merge requests which mention, or will close, the given issue
+pub fn get_related_mrs(project_id: u64, issue_id: u64, gitlabclient: &Client) -> Result<Vec<RelatedMergeRequest>> {
+    let endpoint = RelatedMergeRequests::builder()
+        .project(project_id)
+        .issue(issue_id)
+        .build()
+        .map_err(|e| anyhow!("Could not construct related merge requests query.\n {}", e))?;
+
+    let mut mrs: Vec<RelatedMergeRequest> = api::paged(endpoint, api::Pagination::All)
+        .query(gitlabclient)
+        .context("Failed to find related merge requests")?;
+
+    let endpoint = MergeRequestsClosingIssue::builder()
+        .project(project_id)
+        .issue(issue_id)
+        .build()
+        .map_err(|e| anyhow!("Could not construct closing merge requests query.\n {}", e))?;
+
+    let closing: Vec<RelatedMergeRequest> = api::paged(endpoint, api::Pagination::All)
+        .query(gitlabclient)
+        .context("Failed to find merge requests closing issue")?;
+
+    for mut c in closing {
+        match mrs.iter_mut().find(|m| m.project_id == c.project_id && m.iid == c.iid) {
+            Some(m) => m.closes_issue = true,
+            None => {
+                c.closes_issue = true;
+                mrs.push(c);
+            },
+        }
+    }
+
+    Ok(mrs)
+}
+
+/// Pick the merge request for the issue to check out: that passed, else the only open one in the
+/// project, preferring one which will close the issue, else ask the user
+fn choose_mr(
+    args: &clap::ArgMatches,
+    project_id: u64,
+    issue_id: u64,
+    mrs: Vec<RelatedMergeRequest>,
+) -> Result<RelatedMergeRequest> {
+    let mr_id = args.value_of("mr_id").map(|i| i.parse::<u64>().unwrap());
+
+    let mut candidates = mrs
+        .into_iter()
+        .filter(|m| m.project_id == project_id)
+        .filter(|m| match mr_id {
+            Some(id) => m.iid == id,
+            None => m.state == "opened",
+        })
+        .collect::<Vec<RelatedMergeRequest>>();
+
+    if candidates.iter().filter(|m| m.closes_issue).count() == 1 {
+        candidates.retain(|m| m.closes_issue);
+    }
+
+    match candidates.len() {
+        0 if mr_id.is_some() => Err(anyhow!(
+            "Merge request {} is not related to issue {}",
+            mr_id.unwrap(),
+            issue_id
+        )),
+        0 => Err(anyhow!(
+            "No open merge request for issue {}. Create one with `git lab mr create --issue_id {}`",
+            issue_id,
+            issue_id
+        )),
+        1 => Ok(candidates.remove(0)),
+        _ => {
+            let items = candidates
+                .iter()
+                .map(|m| format!("!{} {} ({})", m.iid, m.title, m.source_branch))
+                .collect::<Vec<String>>();
+
+            let selected = Select::new()
+                .with_prompt("Merge request")
+                .items(&items)
+                .default(0)
+                .interact()?;
+
+            Ok(candidates.remove(selected))
+        }
+    }
+}
+
+pub fn checkout_issue_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+    let issue_id = value_t_or_exit!(args, "id", u64);
+
+    debug!("args: {:#?}", args);
+
+    let mrs = get_related_mrs(project_id, issue_id, &gitlabclient)?;
+    let mr = choose_mr(&args, project_id, issue_id, mrs)?;
+
+    checkout_mr(
+        &config,
+        mr.iid,
+        &mr.source_branch,
+        mr.source_project_id,
+        mr.target_project_id,
+        &CheckoutOptions::from_args(&args),
+    )
+}
@@ -8,13 +8,15 @@ use regex::Regex;
 use termimad::*;
 use textwrap::{fill, indent, termwidth};
 
-use crate::cmds::issue::{generate_basic_issue_builder, Issue};
+use crate::cmds::issue::link::get_issue_links;
+use crate::cmds::issue::related::get_related_mrs;
+use crate::cmds::issue::{generate_basic_issue_builder, Issue, LinkedIssue, RelatedMergeRequest};
 use crate::config;
 use crate::config::OutputFormat;
 use crate::gitlab::Issue as GLIssue;
 use crate::gitlab::{api, Client, Query};
 
-fn print_issue(i: Issue) {
+fn print_issue(i: Issue, links: Vec<LinkedIssue>, mrs: Vec<RelatedMergeRequest>) {
     let mut skin = MadSkin::default();
     skin.headers[0].align = Alignment::Left;
     skin.code_block.align = Alignment::Center;
@@ -158,6 +160,63 @@ fn print_issue(i: Issue) {
         println!("{}", &indent_md);
 
     }
+
+    // print linked issues, grouped by type of link, giving the project only where it differs
+    if !links.is_empty() {
+        println!("{}", "Linked issues".bold());
+
+        for link_type in &["blocks", "is_blocked_by", "relates_to"] {
+            for l in links.iter().filter(|l| l.link_type == *link_type) {
+                let reference = match &l.references {
+                    Some(r) if l.project_id != i.project_id => r["full"].as_str().unwrap().to_string(),
+                    _ => format!("#{}", l.iid),
+                };
+
+                println!(
+                    "    {} {} {} {} {}",
+                    format!("{:<13}", link_type.replace("_", " ")).italic().blue(),
+                    reference,
+                    dot,
+                    l.title,
+                    l.state.dimmed(),
+                );
+            }
+        }
+        println!();
+    }
+
+    // print merge requests, those closing the issue first
+    if !mrs.is_empty() {
+        println!("{}", "Merge requests".bold());
+
+        for m in mrs.iter().filter(|m| m.closes_issue).chain(mrs.iter().filter(|m| !m.closes_issue)) {
+            let reference = match &m.references {
+                Some(r) if m.project_id != i.project_id => r["full"].as_str().unwrap().to_string(),
+                _ => format!("!{}", m.iid),
+            };
+            let relation = if m.closes_issue { "closes" } else { "related" };
+            // list responses only carry the pipeline on some GitLab versions
+            let pipeline = m.head_pipeline
+                .as_ref()
+                .and_then(|p| p["status"].as_str())
+                .map(|p| format!(" {} {}", dot, p.dimmed()))
+                .unwrap_or_default();
+
+            println!(
+                "    {} {} {} {} {} {} {}{}",
+                format!("{:<13}", relation).italic().blue(),
+                reference,
+                dot,
+                m.title,
+                dot,
+                m.author["username"].as_str().unwrap().dimmed(),
+                m.state.dimmed(),
+                pipeline,
+            );
+        }
+        println!();
+    }
+
     println!(
         "{} {}",
         "View this issue on GitLab:".italic().dimmed(),
@@ -191,7 +250,21 @@ pub fn show_issue_cmd(
                 .query(&gitlabclient)
                 .context("Failed to find issue")?;
 
-            print_issue(issue);
+            // linked issues are an extra, and the endpoint isn't available everywhere, so show the
+            // issue without them rather than failing
+            let links = get_issue_links(issue.project_id, issue.iid, &gitlabclient)
+                .unwrap_or_else(|e| {
+                    debug!("Not showing linked issues: {:#}", e);
+                    Vec::new()
+                });
+
+            let mrs = get_related_mrs(issue.project_id, issue.iid, &gitlabclient)
+                .unwrap_or_else(|e| {
+                    debug!("Not showing related merge requests: {:#}", e);
+                    Vec::new()
+                });
+
+            print_issue(issue, links, mrs);
             Ok(())
         }
         _ => Err(anyhow!("Bad output format in config")),
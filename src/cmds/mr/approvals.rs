@@ -0,0 +1,169 @@
+use anyhow::{anyhow, Context, Result};
+use clap::value_t_or_exit;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::config;
+use crate::config::OutputFormat;
+use crate::gitlab::{Client, MergeRequestApprovalState, MergeRequestApprovals, Query};
+use crate::utils;
+
+#[derive(Debug, Deserialize)]
+struct User {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Approver {
+    user: User,
+}
+
+#[derive(Debug, Deserialize)]
+struct Approvals {
+    approvals_required: u64,
+    approvals_left: u64,
+    approved_by: Vec<Approver>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApprovalRule {
+    name: String,
+    approvals_required: u64,
+    approved: bool,
+    approved_by: Vec<User>,
+    eligible_approvers: Vec<User>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApprovalState {
+    rules: Vec<ApprovalRule>,
+}
+
+fn usernames(users: &[User]) -> String {
+    users.iter().map(|u| u.username.as_str()).collect::<Vec<&str>>().join(", ")
+}
+
+/// One line summarising whether an approval rule is satisfied and by whom
+fn format_rule(rule: &ApprovalRule, width: usize) -> String {
+    let status = if rule.approved { "satisfied" } else { "pending" };
+    let count = format!("{}/{}", rule.approved_by.len(), rule.approvals_required);
+
+    let who = match (rule.approved_by.is_empty(), rule.eligible_approvers.is_empty()) {
+        (false, _) => format!("approved by {}", usernames(&rule.approved_by)),
+        (true, false) => format!("can be approved by {}", usernames(&rule.eligible_approvers)),
+        (true, true) => String::new(),
+    };
+
+    format!("  {:<width$}  {:<9}  {:>5}  {}", rule.name, status, count, who, width = width)
+        .trim_end()
+        .to_string()
+}
+
+fn print_approvals(approvals: Approvals, state: Option<ApprovalState>) {
+    let approvers = approvals.approved_by.into_iter().map(|a| a.user).collect::<Vec<User>>();
+
+    println!("Approvals required: {}", approvals.approvals_required);
+    println!("Approvals left:     {}", approvals.approvals_left);
+    println!("Approved by:        {}", if approvers.is_empty() { "-".to_string() } else { usernames(&approvers) });
+
+    match state {
+        Some(s) if !s.rules.is_empty() => {
+            let width = s.rules.iter().map(|r| r.name.len()).max().unwrap_or(0);
+
+            println!();
+            println!("Rules:");
+            for rule in &s.rules {
+                println!("{}", format_rule(rule, width));
+            }
+        },
+        _ => (),
+    }
+}
+
+pub fn approvals_mr_cmd(
+    args: clap::ArgMatches,
+    config: config::Config,
+    gitlabclient: Client,
+) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+    let mr_id = value_t_or_exit!(args, "id", u64);
+
+    let endpoint = MergeRequestApprovals::builder()
+        .project(project_id)
+        .merge_request(mr_id)
+        .build()
+        .map_err(|e| anyhow!("Could not construct merge request approvals query.\n {}", e))?;
+
+    // approval rules need GitLab Premium, so carry on without them when they're not available
+    let state_endpoint = MergeRequestApprovalState::builder()
+        .project(project_id)
+        .merge_request(mr_id)
+        .build()
+        .map_err(|e| anyhow!("Could not construct merge request approval state query.\n {}", e))?;
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+    debug!("state endpoint: {:#?}", state_endpoint);
+
+    match config.format {
+        Some(OutputFormat::JSON) => {
+            let mut approvals: Map<String, Value> = endpoint
+                .query(&gitlabclient)
+                .context("Failed to query merge request approvals")?;
+
+            if let Ok(Value::Object(mut state)) = state_endpoint.query(&gitlabclient) {
+                if let Some(rules) = state.remove("rules") {
+                    approvals.insert("rules".to_string(), rules);
+                }
+            }
+
+            println!("{}", Value::Object(approvals));
+            Ok(())
+        },
+
+        Some(OutputFormat::Text) => {
+            let approvals: Approvals = endpoint
+                .query(&gitlabclient)
+                .context("Failed to query merge request approvals")?;
+
+            let state: Option<ApprovalState> = state_endpoint
+                .query(&gitlabclient)
+                .ok();
+
+            print_approvals(approvals, state);
+            Ok(())
+        },
+
+        None => Err(anyhow!("Bad output format in config")),
+    }
+}
+
+#[cfg(test)]
+mod mr_approvals_unit_tests {
+    use rstest::*;
+
+    use super::*;
+
+    fn users(names: &[&str]) -> Vec<User> {
+        names.iter().map(|n| User { username: n.to_string() }).collect()
+    }
+
+    #[rstest(
+        approved, approved_by, eligible, expected,
+        case(true, &["alice", "bob"], &["alice", "bob", "carol"], "  Backend  satisfied    2/2  approved by alice, bob"),
+        case(false, &["alice"], &["alice", "bob"], "  Backend  pending      1/2  approved by alice"),
+        case(false, &[], &["alice", "bob"], "  Backend  pending      0/2  can be approved by alice, bob"),
+        case(false, &[], &[], "  Backend  pending      0/2"),
+    )]
+    fn test_format_rule(approved: bool, approved_by: &[&str], eligible: &[&str], expected: &str) {
+        let rule = ApprovalRule {
+            name: "Backend".to_string(),
+            approvals_required: 2,
+            approved,
+            approved_by: users(approved_by),
+            eligible_approvers: users(eligible),
+        };
+
+        assert_eq!(format_rule(&rule, 7), expected);
+    }
+}
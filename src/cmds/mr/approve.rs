@@ -1,5 +1,6 @@
 use anyhow::{anyhow, Context, Result};
 use clap::value_t_or_exit;
+use dialoguer::Password;
 
 use crate::config;
 use crate::gitlab::{api, Client, ApproveMergeRequest, Query};
@@ -18,6 +19,22 @@ pub fn approve_mr_cmd(
     let mr_id = value_t_or_exit!(args, "id", u64);
     m.merge_request(mr_id);
 
+    if let Some(sha) = args.value_of("sha") {
+        m.sha(sha);
+    }
+
+    // prompt for the password rather than taking it as an argument, to keep it out of the shell
+    // history
+    let password = if args.is_present("password") {
+        Some(Password::new().with_prompt("Password").interact()?)
+    } else {
+        None
+    };
+
+    if let Some(p) = &password {
+        m.approval_password(p.as_str());
+    }
+
     let endpoint = m
         .build()
         .map_err(|e| anyhow!("Could not construct edit query.\n{}", e))?;
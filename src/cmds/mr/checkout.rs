@@ -1,9 +1,165 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
+use git2::build::CheckoutBuilder;
+use git2::{BranchType, Oid, Repository};
 
-use crate::cmds::mr::{checkout_mr, generate_basic_mr_builder, MergeRequest};
+use crate::cmds::mr::{generate_basic_mr_builder, get_source_remote, MergeRequest};
 use crate::config;
 use crate::gitlab::{Client, Query};
 use crate::gitlab::MergeRequest as GLMergeRequest;
+use crate::utils;
+
+/// How to check out a merge request
+#[derive(Debug, Default)]
+pub struct CheckoutOptions<'a> {
+    /// The git remote to fetch the merge request from, if not that of the project it targets
+    pub remote: Option<&'a str>,
+    /// The local branch to check it out to, if not one named after its source branch
+    pub branch: Option<&'a str>,
+    /// Check out the merge request's head without a local branch
+    pub detach: bool,
+    /// Reset an existing local branch to the merge request's head, even if it has diverged
+    pub force: bool,
+    /// Fetch the head from the source branch rather than the merge request's ref, which GitLab
+    /// only writes once it has processed a new merge request
+    pub from_source_branch: bool,
+}
+
+impl<'a> CheckoutOptions<'a> {
+    pub fn from_args(args: &'a clap::ArgMatches) -> Self {
+        CheckoutOptions {
+            remote: args.value_of("remote"),
+            branch: args.value_of("branch"),
+            detach: args.is_present("detach"),
+            force: args.is_present("force"),
+            from_source_branch: false,
+        }
+    }
+}
+
+/// The git remote of the project a merge request targets: that passed, else the fork's remote if
+/// the merge request targets the fork set up with `project fork`, else the attached project's
+fn get_target_remote(remote: Option<&str>, config: &config::Config, target_project_id: u64) -> String {
+    match (remote, &config.fork_remote) {
+        (Some(r), _) => r.to_string(),
+        (None, Some(f)) if Some(target_project_id) == config.fork_project_id => f.clone(),
+        (None, _) => utils::get_gitlab_remote(config),
+    }
+}
+
+/// Fetch the head of a merge request. GitLab keeps this in the project the merge request targets,
+/// whichever project its source branch is in, and even once the source branch is deleted.
+fn fetch_mr_head(repo: &Repository, remote: &str, iid: u64, config: &config::Config) -> Result<Oid> {
+    let refname = format!("refs/remotes/{}/merge-requests/{}", remote, iid);
+    let refspec = format!("+refs/merge-requests/{}/head:{}", iid, refname);
+
+    utils::fetch_refspecs(repo, remote, &[&refspec], config)?;
+
+    repo.refname_to_id(&refname)
+        .with_context(|| format!("Failed to fetch merge request !{} from {}", iid, remote))
+}
+
+/// Fetch a merge request's source branch from its remote
+fn fetch_source_branch(repo: &Repository, remote: &str, branch: &str, config: &config::Config) -> Result<Oid> {
+    let refname = format!("refs/remotes/{}/{}", remote, branch);
+    let refspec = format!("+refs/heads/{}:{}", branch, refname);
+
+    utils::fetch_refspecs(repo, remote, &[&refspec], config)?;
+
+    repo.refname_to_id(&refname)
+        .with_context(|| format!("Failed to fetch branch {} from {}", branch, remote))
+}
+
+/// Fetch a merge request and check it out, either on a local branch, which is created or else
+/// fast-forwarded (or, if forced, reset) to the merge request's head, or on a detached HEAD
+pub fn checkout_mr(
+    config: &config::Config,
+    iid: u64,
+    source_branch: &str,
+    source_project_id: u64,
+    target_project_id: u64,
+    options: &CheckoutOptions,
+) -> Result<()> {
+    let repo_path = config.repo_path.as_ref()
+        .ok_or_else(|| anyhow!("Local repo not found. Are you in the correct directory?"))?;
+
+    let repo = Repository::open(repo_path)
+        .context("Could not find local repo")?;
+
+    let (remote, head) = if options.from_source_branch {
+        let remote = get_source_remote(options.remote, config, source_project_id, target_project_id);
+        let head = fetch_source_branch(&repo, &remote, source_branch, config)?;
+        (remote, head)
+    } else {
+        let remote = get_target_remote(options.remote, config, target_project_id);
+        let head = fetch_mr_head(&repo, &remote, iid, config)?;
+        (remote, head)
+    };
+
+    let branch = options.branch.unwrap_or(source_branch);
+    let existing = match repo.find_branch(branch, BranchType::Local) {
+        Ok(b) if !options.detach => b.get().target(),
+        _ => None,
+    };
+
+    // a local branch which is only ahead of the merge request is checked out as it is, so as not to
+    // lose its commits, while one which is behind is fast-forwarded
+    let target = match existing {
+        Some(local) if !options.force => {
+            let (ahead, behind) = repo.graph_ahead_behind(local, head)?;
+            match (ahead, behind) {
+                (_, 0) => local,
+                (0, _) => head,
+                _ => return Err(anyhow!(
+                    "Local branch {} has diverged from merge request !{}. Use --force to reset it, or --branch to use another.",
+                    branch,
+                    iid
+                )),
+            }
+        },
+        _ => head,
+    };
+    let commit = repo.find_commit(target)?;
+
+    // update the working tree first, so any local changes which would be overwritten stop the
+    // checkout before any branch is touched
+    repo.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().safe()))
+        .context("Failed to check out merge request. Commit or stash your local changes first.")?;
+
+    if options.detach {
+        repo.set_head_detached(head)?;
+        println!("HEAD is now at {} (merge request !{})", &head.to_string()[..8], iid);
+        return Ok(())
+    }
+
+    let log_message = format!("git-lab: checkout merge request !{}", iid);
+    match existing {
+        Some(_) => {
+            repo.reference(&format!("refs/heads/{}", branch), target, true, &log_message)?;
+        },
+        None => {
+            repo.branch(branch, &commit, false)?;
+
+            // track the source branch, where it's in a project with a known remote
+            let source_remote = if options.from_source_branch || source_project_id == target_project_id {
+                Some(remote)
+            } else if Some(source_project_id) == config.fork_project_id {
+                config.fork_remote.clone()
+            } else {
+                None
+            };
+
+            if let Some(r) = source_remote {
+                let mut repo_config = repo.config()?;
+                repo_config.set_str(&format!("branch.{}.remote", branch), &r)?;
+                repo_config.set_str(&format!("branch.{}.merge", branch), &format!("refs/heads/{}", source_branch))?;
+            }
+        },
+    }
+
+    repo.set_head(&format!("refs/heads/{}", branch))?;
+    println!("Switched to branch {} (merge request !{})", branch, iid);
+    Ok(())
+}
 
 pub fn checkout_merge_request_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
     let mut p = GLMergeRequest::builder();
@@ -16,7 +172,113 @@ pub fn checkout_merge_request_cmd(args: clap::ArgMatches, config: config::Config
         .query(&gitlabclient)
         .context("Failed to find merge request")?;
 
-    checkout_mr(&mr.source_branch)?;
-    Ok(())
+    checkout_mr(
+        &config,
+        mr.iid,
+        &mr.source_branch,
+        mr.source_project_id,
+        mr.target_project_id,
+        &CheckoutOptions::from_args(&args),
+    )
 }
 
+
+#[cfg(test)]
+mod mr_checkout_unit_tests {
+    use std::path::Path;
+    use std::process::Command;
+
+    use assert_fs::TempDir;
+
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    }
+
+    fn config(repo_path: &Path) -> config::Config {
+        config::Config {
+            token: None,
+            host: None,
+            tls: None,
+            format: None,
+            repo_path: Some(repo_path.to_path_buf()),
+            path_with_namespace: None,
+            user_config_type: None,
+            projectid: Some(1),
+            defaultbranch: Some("main".to_string()),
+            remote: Some("origin".to_string()),
+            labels: Vec::new(),
+            members: Vec::new(),
+            fork_project_id: None,
+            fork_remote: None,
+            draft_prefix: None,
+        }
+    }
+
+    /// A clone of a repo with merge request !1, whose head is one commit on from main
+    fn setup() -> (TempDir, std::path::PathBuf, String, String) {
+        let dir = TempDir::new().unwrap();
+        let origin = dir.path().join("origin");
+        let local = dir.path().join("local");
+
+        std::fs::create_dir(&origin).unwrap();
+        git(&origin, &["init", "-q", "-b", "main"]);
+        git(&origin, &["commit", "-q", "--allow-empty", "-m", "first"]);
+        let base = git(&origin, &["rev-parse", "HEAD"]);
+        git(&origin, &["checkout", "-q", "-b", "feature"]);
+        git(&origin, &["commit", "-q", "--allow-empty", "-m", "second"]);
+        let mr_head = git(&origin, &["rev-parse", "HEAD"]);
+        git(&origin, &["update-ref", "refs/merge-requests/1/head", &mr_head]);
+        git(&origin, &["checkout", "-q", "main"]);
+
+        git(dir.path(), &["clone", "-q", origin.to_str().unwrap(), local.to_str().unwrap()]);
+
+        (dir, local, base, mr_head)
+    }
+
+    #[test]
+    fn test_checkout_mr_fast_forwards_branch_behind() {
+        let (_dir, local, base, mr_head) = setup();
+        git(&local, &["branch", "feature", &base]);
+
+        checkout_mr(&config(&local), 1, "feature", 1, 1, &CheckoutOptions::default()).unwrap();
+
+        assert_eq!(git(&local, &["rev-parse", "feature"]), mr_head);
+        assert_eq!(git(&local, &["symbolic-ref", "--short", "HEAD"]), "feature");
+    }
+
+    #[test]
+    fn test_checkout_mr_keeps_branch_ahead() {
+        let (_dir, local, _, mr_head) = setup();
+        git(&local, &["branch", "feature", &mr_head]);
+        git(&local, &["checkout", "-q", "feature"]);
+        git(&local, &["commit", "-q", "--allow-empty", "-m", "local work"]);
+        let local_head = git(&local, &["rev-parse", "HEAD"]);
+        git(&local, &["checkout", "-q", "main"]);
+
+        checkout_mr(&config(&local), 1, "feature", 1, 1, &CheckoutOptions::default()).unwrap();
+
+        assert_eq!(git(&local, &["rev-parse", "feature"]), local_head);
+        assert_eq!(git(&local, &["symbolic-ref", "--short", "HEAD"]), "feature");
+    }
+
+    #[test]
+    fn test_checkout_mr_refuses_diverged_branch() {
+        let (_dir, local, base, _) = setup();
+        git(&local, &["checkout", "-q", "-b", "feature", &base]);
+        git(&local, &["commit", "-q", "--allow-empty", "-m", "local work"]);
+        let local_head = git(&local, &["rev-parse", "HEAD"]);
+        git(&local, &["checkout", "-q", "main"]);
+
+        assert!(checkout_mr(&config(&local), 1, "feature", 1, 1, &CheckoutOptions::default()).is_err());
+        assert_eq!(git(&local, &["rev-parse", "feature"]), local_head);
+    }
+}
@@ -3,18 +3,21 @@ use std::path::PathBuf;
 use anyhow::{anyhow, Context,  Result};
 use clap::value_t;
 use dialoguer::{Confirm, Input, Editor, MultiSelect};
-use git2::{Branch, Repository};
+use git2::{Branch, Repository, Sort};
 use graphql_client::GraphQLQuery;
 use serde::Deserialize;
 use slugify::slugify;
 
 use crate::cmds::issue::generate_basic_issue_builder;
+use crate::cmds::templates::{get_template, TemplateType};
 use crate::config;
-use crate::gitlab::{Client, CreateMergeRequest, Query};
+use crate::gitlab::{api, Client, CreateMergeRequest, Query, UpdateMergeRequest};
 use crate::gitlab::Issue as GLIssue;
+use crate::gitlab::MergeRequest as GLMergeRequest;
 use crate::gitlab::Branch as GLBranch;
 use crate::gitlab::CreateBranch as GLCreateBranch;
-use crate::mr::{checkout_mr, MergeRequest};
+use crate::mr::{checkout_mr, CheckoutOptions, MergeRequest};
+use crate::cmds::mr::quick_edit::draft_title;
 use crate::utils;
 
 #[derive(GraphQLQuery)]
@@ -98,22 +101,64 @@ fn get_commit_details(repo_path: &PathBuf) -> Result<(Option<String>, Option<Str
     }
 }
 
-fn get_current_local_branch_name(repo_path: &PathBuf) -> Result<String> {
-    debug!("get_current_local_branch_name");
+/// Return the subject and body of each commit on HEAD since it branched off `base`, oldest first,
+/// leaving out merge commits
+fn get_branch_commits(repo_path: &PathBuf, base: &str) -> Result<Vec<(String, Option<String>)>> {
     let repo = Repository::open(&repo_path)
         .context("Could not find local repo")?;
-    let head = repo.head()
-        .context("Could not find HEAD of local repo")?;
+    let head = repo.head()?.peel_to_commit()?.id();
+    let base = repo.revparse_single(base)?.peel_to_commit()?.id();
+    let merge_base = repo.merge_base(head, base)?;
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(head)?;
+    revwalk.hide(merge_base)?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+
+    let mut commits = Vec::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
 
-    if head.is_branch() {
-        let b = Branch::wrap(head);
-        let b_name = b.name()
-            .context("Could not find the branch name of the current HEAD")?;
-        let b_name = b_name.
-            ok_or_else(|| anyhow!("Could not extract branch name"))?;
-        Ok(b_name.to_string())
-    } else {
-        Err(anyhow!("Could not find current local branch"))
+        let message = commit.message().unwrap_or_default();
+        let mut lines = message.lines();
+        let subject = lines.next().unwrap_or_default().to_string();
+        let body = lines.collect::<Vec<&str>>().join("\n").trim().to_string();
+
+        commits.push((subject, if body.is_empty() { None } else { Some(body) }));
+    }
+
+    Ok(commits)
+}
+
+/// Format commits as a markdown list of their subjects, with any bodies folded away beneath them
+fn format_commit_summary(commits: &[(String, Option<String>)]) -> String {
+    commits
+        .iter()
+        .map(|(subject, body)| match body {
+            Some(b) => format!(
+                "- <details><summary>{}</summary>\n\n{}\n\n  </details>",
+                subject,
+                b.lines()
+                    .map(|l| if l.is_empty() { String::new() } else { format!("  {}", l) })
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            ),
+            None => format!("- {}", subject),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Put the description template, if any, ahead of the rest of the description
+fn compose_description(template: Option<&str>, body: Option<&str>) -> Option<String> {
+    match (template, body) {
+        (Some(t), Some(b)) => Some(format!("{}\n\n{}", t.trim_end(), b)),
+        (Some(t), None) => Some(t.to_string()),
+        (None, Some(b)) => Some(b.to_string()),
+        (None, None) => None,
     }
 }
 
@@ -140,16 +185,30 @@ fn get_current_remote_branch_name(repo_path: &PathBuf) -> Result<String> {
         let name = b_name.
             ok_or_else(|| anyhow!("Could not extract branch name"))?;
         debug!("get_current_remote_branch_name - got upstream branch name: {}", name);
-        if name.starts_with("origin/") {
-            Ok(name.replacen("origin/","", 1))
-        } else {
-            Ok(name.to_string())
+        match get_current_tracking_remote(&repo_path) {
+            Some(remote) if name.starts_with(&(remote.clone() + "/")) =>
+                Ok(name.replacen(&(remote + "/"), "", 1)),
+            _ => Ok(name.to_string()),
         }
     } else {
         Err(anyhow!("Could not find current local branch"))
     }
 }
 
+/// Return the name of the remote (i.e. `origin`) that the current local branch tracks, if any
+fn get_current_tracking_remote(repo_path: &PathBuf) -> Option<String> {
+    (|| -> Result<String>
+        {
+            let repo = Repository::open(&repo_path)?;
+            let local = utils::get_current_local_branch_name(&repo_path)?;
+            let remote = repo.branch_upstream_remote(&("refs/heads/".to_string() + &local))?;
+            remote.as_str()
+                .map(|r| r.to_string())
+                .ok_or_else(|| anyhow!("Could not extract remote name"))
+        }
+    )().ok()
+}
+
 fn refs_point_to_same_commit(repo_path: &PathBuf, r1: &str, r2: &str) -> bool {
     (|| -> Result<bool>
         {
@@ -164,10 +223,10 @@ fn refs_point_to_same_commit(repo_path: &PathBuf, r1: &str, r2: &str) -> bool {
 }
 
 /// Return a tuple with local and tracking remote branch configs, if present
-/// stripping any remote prefixes (i.e. `origin/`)
+/// stripping any remote prefixes (i.e. `origin/` or `fork/`)
 fn get_current_branch(repo_path: &PathBuf) -> (Option<String>, Option<String>) {
 
-    let local = get_current_local_branch_name(&repo_path).ok();
+    let local = utils::get_current_local_branch_name(&repo_path).ok();
 
     let remote = if local.is_some() {
         get_current_remote_branch_name(&repo_path).ok()
@@ -180,12 +239,40 @@ fn get_current_branch(repo_path: &PathBuf) -> (Option<String>, Option<String>) {
     (local, remote)
 }
 
+/// Return the source branch of the merge request a stacked merge request is based on, which must
+/// be open and raised from a branch in the same project
+fn get_base_branch(p: u64, iid: u64, gitlabclient: &Client) -> Result<String> {
+    #[derive(Deserialize, Debug)]
+    struct MergeRequest { state: String, source_branch: String, source_project_id: u64 }
+
+    let endpoint = GLMergeRequest::builder()
+        .project(p)
+        .merge_request(iid)
+        .build()
+        .map_err(|e| anyhow!("Could not construct query for this merge request.\n {}",e))?;
+
+    let mr: MergeRequest = endpoint
+        .query(gitlabclient)
+        .with_context(|| format!("Failed to find base merge request !{}", iid))?;
+
+    if mr.state != "opened" {
+        return Err(anyhow!("Base merge request !{} is {}, so cannot merge into it.", iid, mr.state))
+    }
+
+    if mr.source_project_id != p {
+        return Err(anyhow!(
+            "Base merge request !{} was raised from a branch in another project, so cannot merge into it.", iid))
+    }
+
+    Ok(mr.source_branch)
+}
+
 fn branch_prefixed_with_issue_id(branch: &str, id: u64) -> bool {
     debug!("branch_prefixed_with_issue_id");
     branch.starts_with(&(id.to_string() + "-"))
 }
 
-fn create_remote_branch(p: u64, from: &str, branch: &str, gitlabclient: &Client) -> Result<String> {
+pub fn create_remote_branch(p: u64, from: &str, branch: &str, gitlabclient: &Client) -> Result<String> {
     debug!("create_remote_branch");
     #[derive(Deserialize, Debug)]
     struct Branch { name: String }
@@ -201,12 +288,112 @@ fn create_remote_branch(p: u64, from: &str, branch: &str, gitlabclient: &Client)
     Ok(branch.name)
 }
 
+/// The number of commits on the current local branch which are not on the remote branch it tracks
+fn unpushed_commits(repo_path: &PathBuf) -> usize {
+    (|| -> Result<usize>
+        {
+            let repo = Repository::open(&repo_path)?;
+            let head = repo.head()?;
+            let local = head.target().ok_or_else(|| anyhow!("HEAD has no target"))?;
+            let upstream = Branch::wrap(head).upstream()?.get().target()
+                .ok_or_else(|| anyhow!("Upstream branch has no target"))?;
+            let (ahead, _) = repo.graph_ahead_behind(local, upstream)?;
+            Ok(ahead)
+        }
+    )().unwrap_or(0)
+}
+
+/// Decide whether to push the local branch, from `--push`/`--no_push`, or else by asking the user
+/// when interactive, and pushing when not
+fn should_push(args: &clap::ArgMatches, interactive: bool, prompt: &str) -> Result<bool> {
+    if args.is_present("push") {
+        Ok(true)
+    } else if args.is_present("no_push") {
+        Ok(false)
+    } else if !interactive {
+        Ok(true)
+    } else {
+        Ok(Confirm::new()
+            .with_prompt(prompt)
+            .default(true)
+            .show_default(true)
+            .interact()?)
+    }
+}
+
+/// Push the local branch to the given branch on the remote, setting it as the upstream
+fn push_branch(remote: &str, local: &str, branch: &str) -> Result<String> {
+    debug!("push_branch");
+    utils::run_git(&["push", "--set-upstream", remote, &format!("{}:{}", local, branch)])?;
+
+    println!("Pushed {} to {}/{}", local, remote, branch);
+    Ok(branch.to_string())
+}
+
+/// Prompt for a merge request title, optionally pre-filled
+pub fn prompt_title(initial: Option<String>) -> Result<String> {
+    let mut input = Input::<String>::new();
+    input.with_prompt("Title").allow_empty(false);
+    if let Some(i) = initial {
+        input.with_initial_text(i);
+    }
+    Ok(input.interact()?)
+}
+
+/// Open the merge request description in $EDITOR. Returns `None` if it was not saved.
+pub fn edit_description(initial: &str) -> Result<Option<String>> {
+    Ok(Editor::new()
+        .extension(".md")
+        .require_save(true)
+        .edit(initial)?)
+}
+
+/// Prompt for labels out of those cached for the project, with `checked` preselected
+pub fn select_labels(labels: &[String], checked: &[String]) -> Result<Vec<String>> {
+    let items = labels
+        .iter()
+        .map(|l| (l.as_str(), checked.contains(l)))
+        .collect::<Vec<(&str, bool)>>();
+
+    let selected = MultiSelect::new()
+        .with_prompt("Label(s)")
+        .items_checked(&items[..])
+        .interact()?;
+
+    Ok(selected.iter().map(|x| labels[*x].clone()).collect())
+}
+
+/// Prompt for users out of the cached project members, with the ids in `checked` preselected,
+/// returning the selected users' ids
+pub fn select_members(prompt: &str, members: &[String], checked: &[u64]) -> Result<Vec<u64>> {
+    // cached project members look like "1234:username"
+    let members = members
+        .iter()
+        .map(|s| {
+            let v = s.split(':').collect::<Vec<&str>>();
+            (v[0].parse::<u64>().unwrap(), v[1])
+        })
+        .collect::<Vec<(u64, &str)>>();
+
+    let items = members
+        .iter()
+        .map(|(id, name)| (*name, checked.contains(id)))
+        .collect::<Vec<(&str, bool)>>();
+
+    let selected = MultiSelect::new()
+        .with_prompt(prompt)
+        .items_checked(&items[..])
+        .interact()?;
+
+    Ok(selected.iter().map(|x| members[*x].0).collect())
+}
+
 fn slug(s: &str) -> String {
     debug!("slug");
     slugify!(s)
 }
 
-fn slug_and_prefix(id: u64, s: &str) -> String {
+pub fn slug_and_prefix(id: u64, s: &str) -> String {
     debug!("slug_and_prefix");
     format!("{}-{}", id, slug(&s))
 }
@@ -239,6 +426,31 @@ pub fn create_merge_request_cmd(
     debug!("Local branch name: {:#?}", local_branch_name);
     debug!("Remote branch name: {:#?}", remote_branch_name);
 
+    // when working from a fork, the source branch lives in the fork and the merge request targets
+    // the attached (upstream) project
+    let source_project_id = match (
+        config.fork_project_id,
+        config.fork_remote.as_ref(),
+        get_current_tracking_remote(&config.repo_path.as_ref().unwrap())
+    ) {
+        (Some(fork_id), _, None) => fork_id,
+        (Some(fork_id), Some(fork_remote), Some(remote)) if *fork_remote == remote => fork_id,
+        _ => project_id,
+    };
+
+    debug!("Source project ID: {:#?}", source_project_id);
+
+    // push to the remote the current branch already tracks, otherwise to the remote of the source
+    // project
+    let push_remote = match get_current_tracking_remote(&config.repo_path.as_ref().unwrap()) {
+        Some(remote) => remote,
+        None if source_project_id != project_id && config.fork_remote.is_some() =>
+            config.fork_remote.clone().unwrap(),
+        None => utils::get_gitlab_remote(&config),
+    };
+
+    debug!("Push remote: {:#?}", push_remote);
+
     let issue_arg = value_t!(args, "issue_id", u64).ok();
 
     debug!("Issue arg: {:#?}", issue_arg);
@@ -268,6 +480,16 @@ pub fn create_merge_request_cmd(
 
     debug!("Issue title: {:#?}", issue_title);
 
+    // a stacked merge request targets the source branch of the one it's based on, and any branch
+    // created for it starts from there too
+    let base_branch = match value_t!(args, "base", u64).ok() {
+        Some(b) => Some(get_base_branch(project_id, b, &gitlabclient)?),
+        None => None,
+    };
+    let branch_from: &str = base_branch.as_deref().unwrap_or(defaultbranch);
+
+    debug!("Base branch: {:#?}", base_branch);
+
     let interactive_title: String;
 
     let title = match (args.value_of("title"), issue_arg) {
@@ -277,16 +499,9 @@ pub fn create_merge_request_cmd(
             if commit_head.is_some()
             && local_branch_name != Some(defaultbranch.to_string())
             {
-                interactive_title = Input::<String>::new()
-                    .with_prompt("Title")
-                    .allow_empty(false)
-                    .with_initial_text(commit_head.unwrap())
-                    .interact()?;
+                interactive_title = prompt_title(commit_head)?;
             } else {
-                interactive_title = Input::<String>::new()
-                    .with_prompt("Title")
-                    .allow_empty(false)
-                    .interact()?;
+                interactive_title = prompt_title(None)?;
             }
 
             Ok(interactive_title)
@@ -295,6 +510,41 @@ pub fn create_merge_request_cmd(
 
     debug!("Title: {:#?}", title);
 
+    // when interactive, offer to describe the merge request with all the commits on the branch,
+    // rather than just the last one, and start from a description template, picking one for
+    // non-interactive use only if one is asked for, as `issue create` does
+    let interactive = !args.is_present("title");
+    let template = if args.value_of("desc").is_none() && (interactive || args.is_present("template")) {
+        get_template(&args, TemplateType::MergeRequest, project_id, &config, &gitlabclient)?
+    } else {
+        None
+    };
+
+    let commits = match &local_branch_name {
+        Some(l) if l != *defaultbranch && args.value_of("desc").is_none() => {
+            let base = format!("{}/{}", utils::get_gitlab_remote(&config), branch_from);
+            get_branch_commits(&config.repo_path.as_ref().unwrap(), &base)
+                .or_else(|_| get_branch_commits(&config.repo_path.as_ref().unwrap(), branch_from))
+                .unwrap_or_default()
+        },
+        _ => Vec::new(),
+    };
+
+    debug!("Commits: {:#?}", commits);
+
+    if interactive
+        && commits.len() > 1
+        && Confirm::new()
+            .with_prompt(format!("Describe the merge request with the {} commits on this branch?", commits.len()))
+            .default(true)
+            .show_default(true)
+            .interact()?
+    {
+        commit_body = Some(format_commit_summary(&commits));
+    }
+
+    let commit_body = compose_description(template.as_deref(), commit_body.as_deref());
+
     let description = match (args.value_of("desc"), args.value_of("issue_id")) {
         (Some(d), Some(i)) => Some(d.to_string() + "\n\nCloses #" +  i),
         (None, Some(i)) => {
@@ -305,14 +555,8 @@ pub fn create_merge_request_cmd(
                     .interact()?
             {
                 match commit_body {
-                    Some(body) => Editor::new()
-                        .extension(".md")
-                        .require_save(true)
-                        .edit(&(body + &"\n\nCloses #".to_string() + i))?,
-                    None => Editor::new()
-                        .extension(".md")
-                        .require_save(true)
-                        .edit(&("<!-- insert MR description here - save and quit when done -->\n\nCloses #".to_string() + i))?,
+                    Some(body) => edit_description(&(body + &"\n\nCloses #".to_string() + i))?,
+                    None => edit_description(&("<!-- insert MR description here - save and quit when done -->\n\nCloses #".to_string() + i))?,
                 }
             } else {
                 match commit_body {
@@ -330,14 +574,11 @@ pub fn create_merge_request_cmd(
                     .interact()?
             {
                 match commit_body {
-                    Some(body) if local_branch_name != Some(defaultbranch.to_string()) => Editor::new()
-                        .extension(".md")
-                        .require_save(true)
-                        .edit(&body)?,
-                    _ => Editor::new()
-                        .extension(".md")
-                        .require_save(true)
-                        .edit("<!-- insert MR description here - save and quit when done -->")?,
+                    Some(body) if local_branch_name != Some(defaultbranch.to_string()) => edit_description(&body)?,
+                    _ => match &template {
+                        Some(t) => edit_description(t)?,
+                        None => edit_description("<!-- insert MR description here - save and quit when done -->")?,
+                    },
                 }
             } else {
                 match commit_body {
@@ -352,7 +593,7 @@ pub fn create_merge_request_cmd(
 
     let target_branch = match (
         args.value_of("target_branch"),
-        defaultbranch
+        base_branch.as_deref()
     )
     {
         (Some(t), _) if remote_branch_exists(project_id, t, &gitlabclient) => Ok(t),
@@ -360,7 +601,8 @@ pub fn create_merge_request_cmd(
             "Branch {} does not exist in the remote (GitLab), so cannot merge into it.",
             t
         ))),
-        (None, _) => Ok(defaultbranch.as_str()),
+        (None, Some(b)) => Ok(b),
+        (None, None) => Ok(defaultbranch.as_str()),
     }?;
 
     debug!("Target branch: {:#?}", target_branch);
@@ -375,16 +617,26 @@ pub fn create_merge_request_cmd(
         remote_branch_name,
         issue_arg);
 
+    // the local commits only end up in the merge request if the local branch is pushed, so offer
+    // that before falling back to creating the source branch from the default branch
+    let push_or_create_remote_branch = |local: &str, branch: &str| -> Result<String> {
+        if should_push(&args, interactive, &format!("Push local branch {} to {}/{}?", local, push_remote, branch))? {
+            push_branch(&push_remote, local, branch)
+        } else {
+            create_remote_branch(source_project_id, branch_from, branch, &gitlabclient)
+        }
+    };
+
     let source_branch: String = match (
         args.value_of("source_branch"),
-        local_branch_name,
-        remote_branch_name,
+        local_branch_name.clone(),
+        remote_branch_name.clone(),
         issue_arg
     ) {
         // Explicitly passed source branch
 
         (Some(s), _, _, None)
-            if remote_branch_exists(project_id, s, &gitlabclient)
+            if remote_branch_exists(source_project_id, s, &gitlabclient)
                 && !open_mr_on_branch(project_path, s, &gitlabclient)
                 =>
                 {
@@ -398,7 +650,7 @@ pub fn create_merge_request_cmd(
                 "Passed branch {} must start with `{}-` to be associated with the issue.", s, i_id))),
 
         (Some(s), _, _, Some(i_id))
-            if remote_branch_exists(project_id, s, &gitlabclient)
+            if remote_branch_exists(source_project_id, s, &gitlabclient)
                 && !open_mr_on_branch(project_path, s, &gitlabclient)
                 && branch_prefixed_with_issue_id(s, i_id)
                 =>
@@ -408,20 +660,22 @@ pub fn create_merge_request_cmd(
                 }
 
         (Some(s), _, _, _)
-            if remote_branch_exists(project_id, s, &gitlabclient)
+            if remote_branch_exists(source_project_id, s, &gitlabclient)
                 && open_mr_on_branch(project_path, s, &gitlabclient)
                 =>
             Err(anyhow!(format!(
                 "Passed branch {} is already a source for an open merge request on the server.", s))),
 
-        (Some(s), _, _, _)=> create_remote_branch(project_id, defaultbranch, s, &gitlabclient),
+        (Some(s), Some(local), _, _) if s == local => push_or_create_remote_branch(&local, s),
+
+        (Some(s), _, _, _)=> create_remote_branch(source_project_id, branch_from, s, &gitlabclient),
 
         // No source branch explicitly passed, so try to infer or create it using the tracking
         // remote branch
 
         // handle the case where an issue_id is passed
         (None, Some(_), Some(remote), Some(i_id))
-            if remote_branch_exists(project_id, &remote, &gitlabclient)
+            if remote_branch_exists(source_project_id, &remote, &gitlabclient)
                 && branch_prefixed_with_issue_id(&remote, i_id) // assumed not to be master
                 =>
                 {
@@ -430,7 +684,7 @@ pub fn create_merge_request_cmd(
                 }
 
         (None, Some(_), Some(remote), Some(i_id))
-            if remote_branch_exists(project_id, &remote, &gitlabclient)
+            if remote_branch_exists(source_project_id, &remote, &gitlabclient)
                 && !branch_prefixed_with_issue_id(&remote, i_id)
                 && &remote != *defaultbranch
                 =>
@@ -441,7 +695,7 @@ pub fn create_merge_request_cmd(
 
         // handle the case where a remote tracking branch exists
         (None, Some(_), Some(remote), None)
-            if remote_branch_exists(project_id, &remote, &gitlabclient)
+            if remote_branch_exists(source_project_id, &remote, &gitlabclient)
                 // this implies that it's not the default (master) branch
                 && open_mr_on_branch(project_path, &remote, &gitlabclient)
                 =>
@@ -449,7 +703,7 @@ pub fn create_merge_request_cmd(
                     "Remote branch {} is already a source for an open merge request on the server.", remote))),
 
         (None, Some(_), Some(remote), None)
-            if remote_branch_exists(project_id, &remote, &gitlabclient)
+            if remote_branch_exists(source_project_id, &remote, &gitlabclient)
                 && &remote != *defaultbranch
                 =>
                 {
@@ -458,34 +712,32 @@ pub fn create_merge_request_cmd(
                 }
 
         (None, Some(_), Some(remote), None)
-            if remote_branch_exists(project_id, &remote, &gitlabclient)
+            if remote_branch_exists(source_project_id, &remote, &gitlabclient)
                 && &remote == *defaultbranch
                 =>
                 {
                     debug!("4a None Some(_) Some({}) None", remote);
                     debug!("Creating remote branch...");
-                    create_remote_branch(project_id, defaultbranch, &slug(&title), &gitlabclient)
+                    create_remote_branch(source_project_id, branch_from, &slug(&title), &gitlabclient)
                 }
 
         // handle the case where a remote tracking branch is present locally but does not exist on
         // the server, probably because it was deleted on the server
-        (None, Some(_), Some(remote), Some(i_id))
-            if !remote_branch_exists(project_id, &remote, &gitlabclient)
+        (None, Some(local), Some(remote), Some(i_id))
+            if !remote_branch_exists(source_project_id, &remote, &gitlabclient)
                 // && branch_prefixed_with_issue_id(&remote, i_id)
                 =>
                 {
-                    debug!("5 None Some(_) Some({}) Some({})", remote, i_id);
-                    debug!("Creating remote branch...");
-                    create_remote_branch(project_id, defaultbranch, &remote, &gitlabclient)
+                    debug!("5 None Some({}) Some({}) Some({})", local, remote, i_id);
+                    push_or_create_remote_branch(&local, &remote)
                 }
 
-        (None, Some(_), Some(remote), None)
-            if !remote_branch_exists(project_id, &remote, &gitlabclient)
+        (None, Some(local), Some(remote), None)
+            if !remote_branch_exists(source_project_id, &remote, &gitlabclient)
                 =>
                 {
-                    debug!("6 None Some(_) Some({}) None)", remote);
-                    debug!("Creating remote branch...");
-                    create_remote_branch(project_id, defaultbranch, &remote, &gitlabclient)
+                    debug!("6 None Some({}) Some({}) None)", local, remote);
+                    push_or_create_remote_branch(&local, &remote)
                 }
 
         // No source branch explicitly passed, so try to infer or create it using the local branch,
@@ -495,8 +747,7 @@ pub fn create_merge_request_cmd(
                 =>
                 {
                     debug!("7 None Some({}) None None)", local);
-                    debug!("Creating remote branch...");
-                    create_remote_branch(project_id, defaultbranch, &local, &gitlabclient)
+                    push_or_create_remote_branch(&local, &local)
                 }
 
         (None, Some(local), None, Some(i_id))
@@ -507,7 +758,7 @@ pub fn create_merge_request_cmd(
 
         (None, Some(local), _, Some(i_id))
             if &local == *defaultbranch
-               && remote_branch_exists(project_id, &slug_and_prefix(i_id, &title), &gitlabclient)
+               && remote_branch_exists(source_project_id, &slug_and_prefix(i_id, &title), &gitlabclient)
                 =>
             Err(anyhow!(format!(
                 "Remote branch {} exists on the server and is already associated to issue #{}",
@@ -515,34 +766,33 @@ pub fn create_merge_request_cmd(
 
         (None, Some(local), _, Some(i_id))
             if &local == *defaultbranch
-               && !remote_branch_exists(project_id, &slug_and_prefix(i_id, &title), &gitlabclient)
+               && !remote_branch_exists(source_project_id, &slug_and_prefix(i_id, &title), &gitlabclient)
                 =>
                 {
                     debug!("8 None Some({}) None Some({})", local, i_id);
                     debug!("Creating remote branch...");
-                    create_remote_branch(project_id, defaultbranch, &slug_and_prefix(i_id, &title), &gitlabclient)
+                    create_remote_branch(source_project_id, branch_from, &slug_and_prefix(i_id, &title), &gitlabclient)
                 }
 
         (None, Some(local), None, None)
             if &local != *defaultbranch
-               && !remote_branch_exists(project_id, &local, &gitlabclient)
+               && !remote_branch_exists(source_project_id, &local, &gitlabclient)
                 =>
                 {
                     debug!("9 None Some({}) None None", local);
-                    debug!("Creating remote branch...");
-                    create_remote_branch(project_id, defaultbranch, &local, &gitlabclient)
+                    push_or_create_remote_branch(&local, &local)
                 }
 
         // no explicit source branch or issue created, and on the master branch,
         // so create the source branch from the title
         (None, Some(local), _, None)
             if &local == *defaultbranch
-               && !remote_branch_exists(project_id, &slug(&title), &gitlabclient)
+               && !remote_branch_exists(source_project_id, &slug(&title), &gitlabclient)
                 =>
                 {
                     debug!("10 None Some({}) None None", local);
                     debug!("Creating remote branch...");
-                    create_remote_branch(project_id, defaultbranch, &slug(&title), &gitlabclient)
+                    create_remote_branch(source_project_id, branch_from, &slug(&title), &gitlabclient)
                 }
 
         (s, l, r, i)
@@ -555,12 +805,33 @@ pub fn create_merge_request_cmd(
 
     debug!("Source branch: {:#?}", source_branch);
 
+    // the source branch is the one the local branch tracks, but it's missing local commits
+    if let Some(local) = &local_branch_name {
+        let ahead = unpushed_commits(&config.repo_path.as_ref().unwrap());
+        if ahead > 0
+            && remote_branch_name.as_ref() == Some(&source_branch)
+            && should_push(&args, interactive, &format!(
+                "Push {} local commit(s) on {} to {}/{}?", ahead, local, push_remote, source_branch))?
+        {
+            push_branch(&push_remote, local, &source_branch)?;
+        }
+    }
+
     let mut mr = CreateMergeRequest::builder();
     let endpoint = mr
-        .project(project_id)
+        .project(source_project_id)
         .target_branch(target_branch)
-        .source_branch(&source_branch)
-        .title("WIP: ".to_string() + &title);
+        .source_branch(&source_branch);
+
+    if args.is_present("draft") {
+        endpoint.title(draft_title(&title, &config));
+    } else {
+        endpoint.title(&title);
+    }
+
+    if source_project_id != project_id {
+        endpoint.target_project_id(project_id);
+    };
 
     if let Some(d) = description {
         endpoint.description(d);
@@ -578,17 +849,10 @@ pub fn create_merge_request_cmd(
         endpoint.labels(args.values_of("labels").unwrap());
 
     } else if !config.labels.is_empty() {
-        let labels = MultiSelect::new()
-            .with_prompt("Label(s)")
-            .items(&config.labels[..])
-            .interact()?;
+        let labels = select_labels(&config.labels, &[])?;
 
         if !labels.is_empty() {
-            endpoint.labels(
-                labels
-                .iter()
-                .map(|x| config.labels[*x].clone())
-            );
+            endpoint.labels(labels.into_iter());
         }
     }
 
@@ -598,37 +862,21 @@ pub fn create_merge_request_cmd(
         endpoint.assignees(assignee_ids.into_iter());
 
     } else {
-        // pull the cached project member names out of config and present them
-        let assignees = MultiSelect::new()
-            .with_prompt("Assignee(s)")
-            .items(
-                &config.members
-                .iter()
-                .map(|s|
-                    s.split(':')
-                    .collect::<Vec<&str>>()[1]
-                )
-                .collect::<Vec<&str>>()
-            )
-            .interact()?;
-
-        // pull the cached project member ids out of the selected assignees to POST later
+        let assignees = select_members("Assignee(s)", &config.members, &[])?;
+
         if !assignees.is_empty() {
-            endpoint.assignees(
-                assignees
-                .iter()
-                .map(|x|
-                    config.members[*x]
-                    .clone()
-                    .split(':')
-                    .collect::<Vec<&str>>()[0]
-                    .parse::<u64>()
-                    .unwrap()
-                    )
-            );
+            endpoint.assignees(assignees.into_iter());
         }
     }
 
+    // resolve reviewers before creating the merge request so a bad username doesn't leave it
+    // half set up
+    let reviewer_ids = if args.occurrences_of("reviewers") > 0 {
+        utils::map_user_ids_from_names(&config.members, args.values_of("reviewers").unwrap())?
+    } else {
+        Vec::new()
+    };
+
     let endpoint = endpoint
         .build()
         .map_err(|e| anyhow!("Could not construct API call to create merge request.\n {}",e))?;
@@ -642,9 +890,89 @@ pub fn create_merge_request_cmd(
 
     println!("Merge Request created at: {}", merge_request.web_url);
 
+    // the library's create endpoint can't set reviewers, so add them to the new merge request
+    if !reviewer_ids.is_empty() {
+        let endpoint = UpdateMergeRequest::builder()
+            .project(project_id)
+            .merge_request(merge_request.iid)
+            .reviewer_ids(reviewer_ids)
+            .build()
+            .map_err(|e| anyhow!("Could not construct API call to set merge request reviewers.\n {}",e))?;
+
+        debug!("endpoint: {:#?}", endpoint);
+
+        api::ignore(endpoint)
+            .query(&gitlabclient)
+            .context("Failed to set merge request reviewers")?;
+    }
+
     if args.occurrences_of("checkout") > 0 {
-        checkout_mr(&source_branch)?;
+        checkout_mr(
+            &config,
+            merge_request.iid,
+            &source_branch,
+            source_project_id,
+            project_id,
+            &CheckoutOptions { from_source_branch: true, ..CheckoutOptions::default() },
+        )?;
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod mr_create_unit_tests {
+    use rstest::*;
+    use crate::cmds::mr;
+    use crate::subcommand::SubCommand;
+    use clap::SubCommand as ClapSubCommand;
+
+    use super::*;
+
+    #[test]
+    fn test_format_commit_summary() {
+        let commits = vec![
+            ("Add widget".to_string(), None),
+            ("Fix widget".to_string(), Some("It was broken.\n\nNow it isn't.".to_string())),
+        ];
+
+        assert_eq!(
+            format_commit_summary(&commits),
+            "- Add widget\n\
+             - <details><summary>Fix widget</summary>\n\
+             \n  It was broken.\n\
+             \n  Now it isn't.\n\
+             \n  </details>"
+        );
+    }
+
+    #[rstest(
+        template, body, expected,
+        case(Some("## Why\n"), Some("- Add widget"), Some("## Why\n\n- Add widget")),
+        case(Some("## Why"), None, Some("## Why")),
+        case(None, Some("- Add widget"), Some("- Add widget")),
+        case(None, None, None),
+    )]
+    fn test_compose_description(template: Option<&str>, body: Option<&str>, expected: Option<&str>) {
+        assert_eq!(compose_description(template, body).as_deref(), expected);
+    }
+
+    #[rstest(
+        flag, expected,
+        case(None, true),
+        case(Some("--push"), true),
+        case(Some("--no_push"), false),
+    )]
+    fn test_should_push_non_interactive(flag: Option<&str>, expected: bool) {
+        let m_cmd = mr::MergeRequestCmd {
+            clap_cmd: ClapSubCommand::with_name("mr"),
+        };
+
+        let mut argv = vec!["mr", "create", "Fix widget"];
+        argv.extend(flag);
+        let args = m_cmd.gen_clap_command().get_matches_from(argv);
+        let matches = args.subcommand_matches("create").unwrap();
+
+        assert_eq!(should_push(matches, false, "Push?").unwrap(), expected);
+    }
+}
@@ -0,0 +1,247 @@
+use anyhow::{anyhow, Context, Result};
+use clap::value_t_or_exit;
+use dialoguer::{Confirm, Input};
+
+use crate::cmds::mr::create::{edit_description, prompt_title, select_labels, select_members};
+use crate::cmds::mr::{generate_basic_mr_builder, MergeRequest};
+use crate::config;
+use crate::config::OutputFormat;
+use crate::gitlab::MergeRequest as GLMergeRequest;
+use crate::gitlab::{api, Client, Query, UpdateMergeRequest, UpdateMergeRequestBuilder};
+use crate::utils;
+
+pub fn generate_update_mr_builder<'a>(
+    args: &'a clap::ArgMatches,
+    config: &'a config::Config,
+    m: &'a mut UpdateMergeRequestBuilder<'a>,
+) -> Result<UpdateMergeRequest<'a>> {
+
+    for arg in &args.args {
+        let (key, _) = arg;
+        match *key {
+            // straight string arguments
+            "title" => m.title(args.value_of("title").unwrap()),
+            "desc" => m.description(args.value_of("desc").unwrap()),
+            "target_branch" => m.target_branch(args.value_of("target_branch").unwrap()),
+
+            // u64 arguments
+            "milestone_id" => m.milestone_id(value_t_or_exit!(args, "milestone_id", u64)),
+
+            // boolean flags
+            "squash" => m.squash(true),
+            "no_squash" => m.squash(false),
+            "remove_src" => m.remove_source_branch(true),
+            "no_remove_src" => m.remove_source_branch(false),
+
+            // list parameters
+            "add_labels" => m.add_labels(args.values_of("add_labels").unwrap()),
+            "remove_labels" => m.remove_labels(args.values_of("remove_labels").unwrap()),
+            "reviewers" => {
+                let reviewer_ids = utils::map_user_ids_from_names(&config.members, args.values_of("reviewers").unwrap())?;
+                m.reviewer_ids(reviewer_ids)
+            },
+
+            // identifying arguments
+            "id" => m,
+            "project_id" => m,
+            "interactive" => m,
+            "edits" => m,
+            "squashing" => m,
+            "source_removal" => m,
+
+            _ => unreachable!(),
+        };
+    }
+
+    m.build()
+        .map_err(|e| anyhow!("Could not construct merge request edit to send to server.\n {}",e))
+}
+
+/// Prompt for each editable field, starting from the merge request's current values
+fn interactive_update_mr_builder<'a>(
+    mr: &MergeRequest,
+    config: &'a config::Config,
+    m: &'a mut UpdateMergeRequestBuilder<'a>,
+) -> Result<UpdateMergeRequest<'a>> {
+
+    let title = prompt_title(Some(mr.title.clone()))?;
+    if title != mr.title {
+        m.title(title);
+    }
+
+    if Confirm::new()
+        .with_prompt("Edit merge request description?")
+        .default(true)
+        .show_default(true)
+        .interact()?
+    {
+        if let Some(desc) = edit_description(&mr.description.clone().unwrap_or_default())? {
+            m.description(desc);
+        }
+    }
+
+    let target_branch = Input::<String>::new()
+        .with_prompt("Target branch")
+        .allow_empty(false)
+        .with_initial_text(mr.target_branch.clone())
+        .interact()?;
+    if target_branch != mr.target_branch {
+        m.target_branch(target_branch);
+    }
+
+    // offer the cached project labels, plus any on the merge request that aren't cached
+    let mut labels = config.labels.clone();
+    labels.extend(mr.labels.iter().filter(|l| !config.labels.contains(l)).cloned());
+
+    if !labels.is_empty() {
+        let selected = select_labels(&labels, &mr.labels)?;
+        debug!("labels: {:#?}", selected);
+
+        m.add_labels(selected.iter().filter(|l| !mr.labels.contains(l)).cloned());
+        m.remove_labels(mr.labels.iter().filter(|l| !selected.contains(l)).cloned());
+    }
+
+    if !config.members.is_empty() {
+        let current = mr.reviewers
+            .iter()
+            .flatten()
+            .filter_map(|r| r["id"].as_u64())
+            .collect::<Vec<u64>>();
+
+        let selected = select_members("Reviewer(s)", &config.members, &current)?;
+        debug!("reviewers: {:#?}", selected);
+
+        if selected != current {
+            m.reviewer_ids(selected);
+        }
+    }
+
+    let squash = Input::<bool>::new()
+        .with_prompt("Squash commits when merging")
+        .default(mr.squash)
+        .interact()?;
+    if squash != mr.squash {
+        m.squash(squash);
+    }
+
+    let remove_src = mr.force_remove_source_branch.unwrap_or(false);
+    let remove_source_branch = Input::<bool>::new()
+        .with_prompt("Delete source branch when merged")
+        .default(remove_src)
+        .interact()?;
+    if remove_source_branch != remove_src {
+        m.remove_source_branch(remove_source_branch);
+    }
+
+    m.build()
+        .map_err(|e| anyhow!("Could not construct merge request edit to send to server.\n {}",e))
+}
+
+pub fn edit_merge_request_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+    let mr_id = value_t_or_exit!(args, "id", u64);
+
+    let mut m = UpdateMergeRequest::builder();
+    m.project(project_id).merge_request(mr_id);
+
+    let interactive = args.is_present("interactive");
+
+    let endpoint = if !interactive {
+        generate_update_mr_builder(&args, &config, &mut m)?
+    } else {
+        let mut mb = GLMergeRequest::builder();
+        let current_endpoint = generate_basic_mr_builder(&args, "id", &config, &mut mb)?;
+        let mr: MergeRequest = current_endpoint
+            .query(&gitlabclient)
+            .context("Failed to find merge request")?;
+
+        interactive_update_mr_builder(&mr, &config, &mut m)?
+    };
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    match (&config.format, interactive) {
+
+        (_, true) | (Some(OutputFormat::Text), _) => {
+            let merge_request: MergeRequest = endpoint
+                .query(&gitlabclient)
+                .context("Failed to update merge request")?;
+
+            println!("Merge Request URL: {}", merge_request.web_url);
+            Ok(())
+        },
+
+        (Some(OutputFormat::JSON), _) => {
+            let raw_json  = api::raw(endpoint)
+                .query(&gitlabclient)
+                .context("Failed to update merge request")?;
+
+            println!("{}", String::from_utf8(raw_json).unwrap());
+            Ok(())
+        },
+
+        (None, _) => Err(anyhow!("Bad output format in config")),
+    }
+}
+
+#[cfg(test)]
+mod mr_edit_unit_tests {
+    use gitlab::api::Endpoint;
+
+    use crate::cmds::mr;
+    use crate::subcommand::SubCommand;
+    use clap::SubCommand as ClapSubCommand;
+
+    use super::*;
+
+    #[test]
+    fn test_generate_update_mr_builder() {
+        // GIVEN
+        let config = config::Config {
+            token: None,
+            host: None,
+            tls: None,
+            format: None,
+            repo_path: None,
+            path_with_namespace: None,
+            user_config_type: None,
+            projectid: None,
+            defaultbranch: None,
+            remote: None,
+            labels: Vec::new(),
+            members: Vec::new(),
+            fork_project_id: None,
+            fork_remote: None,
+            draft_prefix: None,
+        };
+
+        let mut m = UpdateMergeRequest::builder();
+        m.project(1).merge_request(5);
+
+        let m_cmd = mr::MergeRequestCmd {
+            clap_cmd: ClapSubCommand::with_name("mr"),
+        };
+
+        let args = m_cmd.gen_clap_command().get_matches_from(vec![
+            "mr",
+            "edit",
+            "5",
+            "--title",
+            "New title",
+            "--no_squash",
+            "--remove_src",
+        ]);
+        let matches = args.subcommand_matches("edit");
+
+        // WHEN
+        let endpoint = generate_update_mr_builder(matches.unwrap(), &config, &mut m).unwrap();
+
+        // THEN
+        let (_, body) = endpoint.body().unwrap().unwrap();
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            "title=New+title&squash=false&remove_source_branch=true"
+        );
+    }
+}
@@ -3,13 +3,17 @@ use chrono::{Utc, DateTime, Local};
 use chrono_humanize::HumanTime;
 use clap::{value_t_or_exit, values_t_or_exit};
 use comfy_table::*;
+use serde_json::{Map, Value};
 
 use crate::config;
 use crate::config::OutputFormat;
 use crate::gitlab::converter::{
     mr_order_by_from_str, mr_scope_from_str, mr_state_from_str,
 };
-use crate::gitlab::{api, Client, MergeRequests, MergeRequestsBuilder, Query, SortOrder};
+use crate::gitlab::{
+    api, Client, CurrentUser, MergeRequests, MergeRequestsBuilder, MergeRequestsWithReviewer, Query,
+    SortOrder,
+};
 use crate::utils;
 use crate::cmds::mr::MergeRequest;
 
@@ -61,7 +65,10 @@ pub fn generate_mrs_builder<'a>(
             "created_before" => m.created_before(datefield!("created_before", args)),
             "updated_after" => m.updated_after(datefield!("updated_after", args)),
             "updated_before" => m.updated_before(datefield!("updated_before", args)),
-            "wip" => m.wip(true),
+            "draft" => m.wip(true),
+            "ready" => m.wip(false),
+            "reviewer" => m,
+            "reviewer_me" => m,
             "order_by" => {
                 m.order_by(mr_order_by_from_str(args.value_of("order_by").unwrap()).unwrap())
             }
@@ -78,7 +85,24 @@ pub fn generate_mrs_builder<'a>(
         .map_err(|e| anyhow!("Could not construct merge requests query.\n {}", e))
 }
 
-fn print_mrs(mrs: Vec<MergeRequest>, fields: Vec<String>, no_headers: bool, human: bool) {
+/// The ID of the user to filter merge requests by reviewer, if any
+fn get_reviewer_id(args: &clap::ArgMatches, config: &config::Config, gitlabclient: &Client) -> Result<Option<u64>> {
+    if args.is_present("reviewer") {
+        Ok(Some(utils::map_user_ids_from_names(&config.members, args.values_of("reviewer").unwrap())?[0]))
+    } else if args.is_present("reviewer_me") {
+        let user: Map<String, Value> = CurrentUser::builder()
+            .build()
+            .unwrap()
+            .query(gitlabclient)
+            .context("Failed to find current user")?;
+
+        Ok(user["id"].as_u64())
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn print_mrs(mrs: Vec<MergeRequest>, fields: Vec<String>, no_headers: bool, human: bool) {
     let mut table = Table::new();
 
     table
@@ -191,6 +215,21 @@ fn print_mrs(mrs: Vec<MergeRequest>, fields: Vec<String>, no_headers: bool, huma
                         r.push(Cell::new("n").set_alignment(CellAlignment::Center))
                     }
                 },
+                "reviewers" => {
+                    match &m.reviewers {
+                        Some(reviewers) if !reviewers.is_empty() => {
+                            r.push(
+                                Cell::new(
+                                    reviewers
+                                    .iter()
+                                    .map(|a| a["username"].as_str().unwrap().to_string())
+                                    .collect::<Vec<String>>().join(",")
+                                )
+                            )
+                        },
+                        _ => r.push(Cell::new("-").set_alignment(CellAlignment::Center)),
+                    }
+                },
                 "title" => r.push(Cell::new(m.title.clone())),
                 "source_branch" => r.push(Cell::new(m.source_branch.clone())),
                 "target_branch" => r.push(Cell::new(m.target_branch.clone())),
@@ -202,8 +241,8 @@ fn print_mrs(mrs: Vec<MergeRequest>, fields: Vec<String>, no_headers: bool, huma
                             r.push(Cell::new(d.format("%Y-%m-%d %H:%M:%S").to_string()))
                         }
                 "upvotes" => r.push(Cell::new(m.upvotes).set_alignment(CellAlignment::Right)),
-                "wip" => {
-                    if m.work_in_progress {
+                "draft" | "wip" => {
+                    if m.is_draft() {
                         r.push(Cell::new("y").set_alignment(CellAlignment::Center))
                     } else {
                         r.push(Cell::new("n").set_alignment(CellAlignment::Center))
@@ -231,7 +270,10 @@ pub fn list_mrs_cmd(
     gitlabclient: Client,
 ) -> Result<()> {
     let mut i = MergeRequests::builder();
-    let endpoint = generate_mrs_builder(&args, &config, &mut i)?;
+    let endpoint = MergeRequestsWithReviewer::new(
+        generate_mrs_builder(&args, &config, &mut i)?,
+        get_reviewer_id(&args, &config, &gitlabclient)?,
+    );
     let max = value_t_or_exit!(args, "max", u32);
 
     debug!("args: {:#?}", args);
@@ -1,15 +1,110 @@
 use anyhow::{anyhow, Context, Result};
 use clap::value_t_or_exit;
+use dialoguer::Editor;
+use git2::{BranchType, Oid, Repository};
 
+use crate::cmds::mr::{generate_basic_mr_builder, get_source_remote, MergeRequest};
 use crate::config;
-use crate::gitlab::{api, Client, MergeMergeRequest, Query};
+use crate::gitlab::MergeRequest as GLMergeRequest;
+use crate::gitlab::{api, CancelMergeWhenPipelineSucceeds, Client, MergeMergeRequest, Query};
 use crate::utils;
 
+/// Open a commit message in $EDITOR, failing if it is not saved
+fn edit_message(initial: &str) -> Result<String> {
+    Editor::new()
+        .require_save(true)
+        .edit(initial)?
+        .ok_or_else(|| anyhow!("Commit message not saved, aborting merge"))
+}
+
+/// The merge commit message GitLab would use by default
+fn default_merge_message(mr: &MergeRequest) -> String {
+    format!(
+        "Merge branch '{}' into '{}'\n\n{}\n\nSee merge request {}",
+        mr.source_branch,
+        mr.target_branch,
+        mr.title,
+        mr.references["full"].as_str().unwrap_or_default(),
+    )
+}
+
+/// Check out the default branch, bring it up to date with the remote and delete the local copy of
+/// the merged source branch, unless it has commits which were not merged
+fn clean_up_local_repo(config: &config::Config, mr: &MergeRequest) -> Result<()> {
+    let repo_path = config.repo_path.as_ref()
+        .ok_or_else(|| anyhow!("Local repo not found. Are you in the correct directory?"))?;
+
+    let defaultbranch = config.defaultbranch.as_ref()
+        .ok_or_else(|| anyhow!("Could not determine default remote branch - try `git lab project refresh`"))?;
+
+    let remote = utils::get_gitlab_remote(config);
+    let source_remote = get_source_remote(None, config, mr.source_project_id, mr.target_project_id);
+
+    utils::run_git(&["fetch", "--prune", &remote])?;
+    if source_remote != remote {
+        utils::run_git(&["fetch", "--prune", &source_remote])?;
+    }
+
+    utils::run_git(&["checkout", defaultbranch])?;
+    utils::run_git(&["merge", "--ff-only", &format!("{}/{}", remote, defaultbranch)])?;
+
+    if &mr.source_branch == defaultbranch {
+        return Ok(())
+    }
+
+    let repo = Repository::open(repo_path)
+        .context("Could not find local repo")?;
+
+    let local = match repo.find_branch(&mr.source_branch, BranchType::Local) {
+        Ok(b) => b.get().target(),
+        Err(_) => return Ok(()),
+    };
+    let merged = mr.sha.as_deref().and_then(|s| Oid::from_str(s).ok());
+
+    // squashing or rebasing on merge means git can't tell that the branch was merged, so compare
+    // it with the head of the merge request instead
+    let fully_merged = match (local, merged) {
+        (Some(l), Some(m)) => utils::contained_in_any(&repo, l, &[m]),
+        _ => false,
+    };
+
+    if fully_merged {
+        utils::run_git(&["branch", "-D", &mr.source_branch])
+    } else {
+        println!("Kept local branch {} as it has commits which were not merged", mr.source_branch);
+        Ok(())
+    }
+}
+
+fn cancel_auto_merge_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+    let mr_id = value_t_or_exit!(args, "id", u64);
+
+    let endpoint = CancelMergeWhenPipelineSucceeds::builder()
+        .project(project_id)
+        .merge_request(mr_id)
+        .build()
+        .map_err(|e| anyhow!("Could not construct cancel merge query.\n{}", e))?;
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    api::ignore(endpoint)
+        .query(&gitlabclient)
+        .context("Failed to cancel merge when pipeline succeeds")?;
+
+    Ok(())
+}
+
 pub fn merge_mr_cmd(
     args: clap::ArgMatches,
     config: config::Config,
     gitlabclient: Client,
 ) -> Result<()> {
+    if args.is_present("cancel_auto") {
+        return cancel_auto_merge_cmd(args, config, gitlabclient)
+    }
+
     let mut m = MergeMergeRequest::builder();
 
     let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
@@ -26,6 +121,39 @@ pub fn merge_mr_cmd(
         m.should_remove_source_branch(true);
     }
 
+    if args.is_present("squash") {
+        m.squash(true);
+    }
+
+    if let Some(sha) = args.value_of("sha") {
+        m.sha(sha);
+    }
+
+    let mut merge_message = args.value_of("message").map(|s| s.to_string());
+    let mut squash_message = args.value_of("squash_message").map(|s| s.to_string());
+
+    if args.is_present("edit") {
+        let mut p = GLMergeRequest::builder();
+        let endpoint = generate_basic_mr_builder(&args, "id", &config, &mut p)?;
+        let mr: MergeRequest = endpoint
+            .query(&gitlabclient)
+            .context("Failed to find merge request")?;
+
+        merge_message = Some(edit_message(&merge_message.unwrap_or_else(|| default_merge_message(&mr)))?);
+
+        if args.is_present("squash") || mr.squash {
+            squash_message = Some(edit_message(&squash_message.unwrap_or_else(|| mr.title.clone()))?);
+        }
+    }
+
+    if let Some(message) = &merge_message {
+        m.merge_commit_message(message.as_str());
+    }
+
+    if let Some(message) = &squash_message {
+        m.squash_commit_message(message.as_str());
+    }
+
     let endpoint = m
         .build()
         .map_err(|e| anyhow!("Could not construct edit query.\n{}", e))?;
@@ -33,9 +161,17 @@ pub fn merge_mr_cmd(
     debug!("args: {:#?}", args);
     debug!("endpoint: {:#?}", endpoint);
 
-    api::ignore(endpoint)
-        .query(&gitlabclient)
-        .context("Failed to update merge request")?;
+    if args.is_present("clean_up") {
+        let mr: MergeRequest = endpoint
+            .query(&gitlabclient)
+            .context("Failed to merge merge request")?;
 
-    Ok(())
+        clean_up_local_repo(&config, &mr)
+    } else {
+        api::ignore(endpoint)
+            .query(&gitlabclient)
+            .context("Failed to merge merge request")?;
+
+        Ok(())
+    }
 }
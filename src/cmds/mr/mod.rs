@@ -1,15 +1,19 @@
+mod approvals;
 mod approve;
 mod checkout;
-mod create;
-mod list;
+pub mod create;
+mod edit;
+pub mod list;
 mod merge;
 mod open;
 mod quick_edit;
 mod rebase;
+mod review_request;
 mod show;
+mod stack;
 mod unapprove;
 
-use std::process::{Command, Stdio};
+pub use checkout::{checkout_mr, CheckoutOptions};
 
 use anyhow::{anyhow, Context, Result};
 use clap::ArgGroup;
@@ -21,6 +25,9 @@ use crate::config;
 use crate::gitlab::MergeRequest as GLMergeRequest;
 use crate::gitlab::MergeRequestBuilder;
 use crate::gitlab;
+use crate::gitlab::TimeTrackable;
+use crate::cmds::templates::template_arg;
+use crate::cmds::time_tracking;
 use crate::subcommand;
 use crate::utils;
 use crate::utils::validator;
@@ -33,6 +40,8 @@ pub enum ShortCmd {
     Lock,
     Unlock,
     Wip,
+    Draft,
+    Ready,
 }
 
 #[derive(Debug, Deserialize)]
@@ -40,6 +49,8 @@ pub struct MergeRequest {
     id: u64,
     iid: u64,
     project_id: u64,
+    source_project_id: u64,
+    target_project_id: u64,
     title: String,
     description: Option<String>,
     state: String,
@@ -50,9 +61,10 @@ pub struct MergeRequest {
     closed_by: Option<Map<String, Value>>,
     merged_by: Option<Map<String, Value>>,
     labels: Vec<String>,
-    milestone: Option<String>,
+    milestone: Option<Map<String, Value>>,
     author: Map<String, Value>,
     assignees: Option<Vec<Map<String, Value>>>,
+    reviewers: Option<Vec<Map<String, Value>>>,
     user_notes_count: u64,
     upvotes: u64,
     downvotes: u64,
@@ -63,29 +75,41 @@ pub struct MergeRequest {
     subscribed: Option<bool>,
     target_branch: String,
     source_branch: String,
+    sha: Option<String>,
     work_in_progress: bool,
+    draft: Option<bool>,
     merge_when_pipeline_succeeds: bool,
     merge_status: String,
     has_conflicts: bool,
+    merge_error: Option<String>,
+    rebase_in_progress: Option<bool>,
     blocking_discussions_resolved: bool,
     squash: bool,
+    force_remove_source_branch: Option<bool>,
 }
-pub fn checkout_mr(source_branch: &str) -> Result<()> {
 
-    Command::new("git")
-        .args(&["fetch","origin"])
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()?
-        .wait()?;
-
-    Command::new("git")
-        .args(&["checkout","-b", source_branch, &("origin/".to_string() + source_branch)])
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()?
-        .wait()?;
-    Ok(())
+impl MergeRequest {
+    /// Whether the merge request is a draft, as GitLab sees it. Servers older than 13.2 only
+    /// report the deprecated `work_in_progress` field.
+    fn is_draft(&self) -> bool {
+        self.draft.unwrap_or(self.work_in_progress)
+    }
+}
+/// The git remote to fetch a merge request's source branch from: that passed, else the fork's
+/// remote if the merge request was raised from the fork set up with `project fork`, else the
+/// attached project's remote
+pub fn get_source_remote(
+    remote: Option<&str>,
+    config: &config::Config,
+    source_project_id: u64,
+    target_project_id: u64,
+) -> String {
+    match (remote, &config.fork_remote) {
+        (Some(r), _) => r.to_string(),
+        (None, Some(f)) if Some(source_project_id) == config.fork_project_id
+            && source_project_id != target_project_id => f.clone(),
+        (None, _) => utils::get_gitlab_remote(config),
+    }
 }
 
 pub fn generate_basic_mr_builder<'a>(
@@ -102,6 +126,12 @@ pub fn generate_basic_mr_builder<'a>(
         .map_err(|e| anyhow!("Could not construct query for this merge request.\n {}",e))
 }
 
+const DRAFT_AFTER_HELP: &str =
+"GitLab only allows the draft state to be changed through the merge request's title, so this adds \
+or removes a draft prefix. The prefix added is `Draft:` unless set otherwise with \
+`git config gitlab.draftprefix`, and must be one GitLab recognises, such as `[Draft]`, `(Draft)` or, \
+for servers older than 13.2, `WIP:`.";
+
 pub struct MergeRequestCmd<'a> {
     pub clap_cmd: clap::App<'a, 'a>,
 }
@@ -221,6 +251,20 @@ impl subcommand::SubCommand for MergeRequestCmd<'_> {
                             .long("assigned")
                             .help("Only return merge requests that are assigned")
                     )
+                    .arg(
+                        clap::Arg::with_name("reviewer")
+                            .long("reviewer")
+                            .help("Filter merge requests which a username is asked to review")
+                            .takes_value(true)
+                            .empty_values(false)
+                    )
+                    .arg(
+                        clap::Arg::with_name("reviewer_me")
+                            .long("reviewer_me")
+                            .visible_alias("reviewer-me")
+                            .help("Only return merge requests which you are asked to review")
+                            .conflicts_with("reviewer")
+                    )
                     .arg(
                         clap::Arg::with_name("filter")
                             .long("filter")
@@ -266,10 +310,17 @@ impl subcommand::SubCommand for MergeRequestCmd<'_> {
                             .validator(validator::check_valid_humantime_duration)
                     )
                     .arg(
-                        clap::Arg::with_name("wip")
-                            .long("wip")
+                        clap::Arg::with_name("draft")
+                            .long("draft")
+                            .visible_alias("wip")
                             .short("w")
-                            .help("Fetch merge requests which are works in progress")
+                            .help("Fetch merge requests which are drafts")
+                            .conflicts_with("ready")
+                    )
+                    .arg(
+                        clap::Arg::with_name("ready")
+                            .long("ready")
+                            .help("Fetch merge requests which are not drafts")
                     )
                     .arg(
                         clap::Arg::with_name("fields")
@@ -287,6 +338,7 @@ impl subcommand::SubCommand for MergeRequestCmd<'_> {
                                 "closed_on",
                                 "created_on", // created_at
                                 "downvotes",
+                                "draft",
                                 "has_conflicts",
                                 "id", // iid
                                 "labels",
@@ -294,6 +346,7 @@ impl subcommand::SubCommand for MergeRequestCmd<'_> {
                                 "merged_by",
                                 "merged_on", // merged_at
                                 // "merge_status",
+                                "reviewers",
                                 "state",
                                 "subscribed",
                                 "title",
@@ -354,6 +407,147 @@ impl subcommand::SubCommand for MergeRequestCmd<'_> {
 form: `y, M, d, h, m, s`."
                     ),
             )
+            .subcommand(
+                clap::SubCommand::with_name("edit")
+                    .about("Edits a merge request")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .setting(clap::AppSettings::DeriveDisplayOrder)
+                    // clap rejects arguments in a group alongside one conflicting with another of
+                    // its members, so conflicts are set between groups instead
+                    .group(ArgGroup::with_name("edits")
+                        .multiple(true)
+                        .conflicts_with("interactive")
+                        )
+                    .group(ArgGroup::with_name("squashing"))
+                    .group(ArgGroup::with_name("source_removal"))
+                    .arg(
+                        clap::Arg::with_name("id")
+                            .help("Merge request ID to edit")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("project_id")
+                            .long("project_id")
+                            .short("p")
+                            .help("Project ID to look for merge request in. Defaults to attached Project ID.")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("title")
+                            .long("title")
+                            .help("Sets the merge request title")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .group("edits")
+                    )
+                    .arg(
+                        clap::Arg::with_name("desc")
+                            .long("desc")
+                            .short("d")
+                            .help("Sets the merge request description")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .group("edits")
+                    )
+                    .arg(
+                        clap::Arg::with_name("target_branch")
+                            .long("target")
+                            .short("t")
+                            .help("Sets the branch to merge into")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .group("edits")
+                    )
+                    .arg(
+                        clap::Arg::with_name("milestone_id")
+                            .long("milestone_id")
+                            .short("m")
+                            .help("Associates the merge request to a milestone. 0 removes the milestone")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .group("edits")
+                            .validator(validator::check_u32)
+                    )
+                    .arg(
+                        clap::Arg::with_name("add_labels")
+                            .long("add_labels")
+                            .short("l")
+                            .help("Adds label(s) to the merge request")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .group("edits")
+                            .multiple(true)
+                            .require_delimiter(true)
+                    )
+                    .arg(
+                        clap::Arg::with_name("remove_labels")
+                            .long("remove_labels")
+                            .short("L")
+                            .help("Removes label(s) from the merge request")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .group("edits")
+                            .multiple(true)
+                            .require_delimiter(true)
+                    )
+                    .arg(
+                        clap::Arg::with_name("reviewers")
+                            .long("reviewers")
+                            .help("Username(s) of merge request reviewer(s), replacing any current ones")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .group("edits")
+                            .multiple(true)
+                            .require_delimiter(true)
+                    )
+                    .arg(
+                        clap::Arg::with_name("squash")
+                            .long("squash")
+                            .short("q")
+                            .help("Squash commits when merging")
+                            .groups(&["edits", "squashing"])
+                    )
+                    .arg(
+                        clap::Arg::with_name("no_squash")
+                            .long("no_squash")
+                            .short("Q")
+                            .help("Do not squash commits when merging")
+                            .groups(&["edits", "squashing"])
+                    )
+                    .arg(
+                        clap::Arg::with_name("remove_src")
+                            .long("remove_src")
+                            .short("r")
+                            .help("Delete source branch when merged")
+                            .groups(&["edits", "source_removal"])
+                    )
+                    .arg(
+                        clap::Arg::with_name("no_remove_src")
+                            .long("no_remove_src")
+                            .short("R")
+                            .help("Do not delete source branch when merged")
+                            .groups(&["edits", "source_removal"])
+                    )
+                    .arg(
+                        clap::Arg::with_name("interactive")
+                            .long("interactive")
+                            .short("i")
+                            .help("Prompts for each field, starting from its current value")
+                            .required_unless("edits")
+                    )
+                    .after_help(
+"Only the fields passed are changed. Labels are added and removed individually, leaving any others \
+on the merge request in place.\
+\n
+With `--interactive` the user is prompted to edit each field in turn, starting from the merge \
+request's current values. The description is opened in $EDITOR.",
+                    ),
+            )
             .subcommand(
                 clap::SubCommand::with_name("create")
                     .about("Creates a merge request")
@@ -393,6 +587,16 @@ form: `y, M, d, h, m, s`."
                             .takes_value(true)
                             .empty_values(false)
                     )
+                    .arg(
+                        clap::Arg::with_name("base")
+                            .long("base")
+                            .short("b")
+                            .help("ID of the merge request to stack this one on, targeting its source branch")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .conflicts_with("target_branch")
+                            .validator(validator::check_u64)
+                    )
                     .arg(
                         clap::Arg::with_name("desc")
                             .long("desc")
@@ -401,12 +605,22 @@ form: `y, M, d, h, m, s`."
                             .takes_value(true)
                             .empty_values(false)
                     )
+                    .arg(
+                        template_arg()
+                            .conflicts_with("desc")
+                    )
                     .arg(
                         clap::Arg::with_name("title")
                             .help("Merge request title")
                             .takes_value(true)
                             .empty_values(false)
                     )
+                    .arg(
+                        clap::Arg::with_name("draft")
+                            .help("Create the merge request as a draft")
+                            .long("draft")
+                            .takes_value(false)
+                    )
                     .arg(
                         clap::Arg::with_name("squash")
                             .help("Squash commits when merging")
@@ -428,6 +642,21 @@ form: `y, M, d, h, m, s`."
                             .short("c")
                             .takes_value(false)
                     )
+                    .arg(
+                        clap::Arg::with_name("push")
+                            .help("Push the current local branch before creating the merge request")
+                            .long("push")
+                            .short("P")
+                            .takes_value(false)
+                            .conflicts_with("no_push")
+                    )
+                    .arg(
+                        clap::Arg::with_name("no_push")
+                            .help("Don't push the current local branch - create the source branch on the server instead")
+                            .long("no_push")
+                            .visible_alias("no-push")
+                            .takes_value(false)
+                    )
                     .arg(
                         clap::Arg::with_name("labels")
                             .long("labels")
@@ -448,6 +677,15 @@ form: `y, M, d, h, m, s`."
                             .empty_values(false)
                             .require_delimiter(true)
                     )
+                    .arg(
+                        clap::Arg::with_name("reviewers")
+                            .long("reviewers")
+                            .help("Username(s) of merge request reviewer(s)")
+                            .takes_value(true)
+                            .multiple(true)
+                            .empty_values(false)
+                            .require_delimiter(true)
+                    )
                     .after_help(
 "This command tries to do the right thing by taking into account local and remote repo, branch and \
 commit state. It may create a source branch on the GitLab server if it cannot infer which to use. \
@@ -455,13 +693,93 @@ Where appropriate, it will prompt the user to input required information. It wil
 follow GitLab conventions using branch names, merge request titles and trigger text within the \
 merge request description. \
 \
-NB: The current implementation requires that the GitLab-hosted git remote is called `origin`."
+The merge request is created ready for review, unless `--draft` is passed. \
+\
+The description starts from a template in the project's .gitlab/merge_request_templates \
+directory: the one passed with `--template`, otherwise the project's default template (set in its \
+settings, or named Default.md), otherwise one chosen from those available. When the title is \
+passed, a template is only used if it is passed with `--template`. When the branch has \
+several commits since the default branch, it can be filled in with a list of them, rather than \
+just the body of the last commit. \
+\
+If a fork has been set up with `git lab project fork` and the current branch tracks the fork's \
+remote (or no remote at all), the source branch is taken from the fork and the merge request \
+targets the attached project. \
+\
+With `--base`, the merge request is stacked on another: it targets that merge request's source \
+branch instead of the default branch. See `git lab mr stack`. \
+\
+When the current local branch isn't on the server yet, or has commits which haven't been pushed, \
+it is pushed (setting its upstream) before the merge request is created, so the merge request \
+contains the local commits. Without `--push` or `--no_push` the user is asked first when \
+creating the merge request interactively, and the branch is pushed otherwise. Declining \
+leaves an existing remote branch as it is, or else creates the source branch on the server from \
+the default branch, or from the base merge request's branch with `--base`."
                     ),
             )
+            .subcommand(
+                clap::SubCommand::with_name("draft")
+                    .about("Marks a merge request as a draft")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("id")
+                            .help("Merge request ID")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                            .validator(validator::check_u64)
+                    )
+                    // `draft` used to be an alias of `wip`, so keep its flags working
+                    .arg(
+                        clap::Arg::with_name("off")
+                            .long("off")
+                            .help("Marks as ready, as `mr ready` does")
+                            .conflicts_with("on")
+                            .hidden(true)
+                    )
+                    .arg(
+                        clap::Arg::with_name("on")
+                            .long("on")
+                            .help("Marks as draft")
+                            .hidden(true)
+                    )
+                    .arg(
+                        clap::Arg::with_name("project_id")
+                            .short("p")
+                            .long("project_id")
+                            .help("Project ID to look for merge request in. Defaults to attached Project ID.")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .validator(validator::check_u64)
+                    )
+                    .after_help(DRAFT_AFTER_HELP)
+            )
+            .subcommand(
+                clap::SubCommand::with_name("ready")
+                    .about("Marks a draft merge request as ready")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("id")
+                            .help("Merge request ID")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("project_id")
+                            .short("p")
+                            .long("project_id")
+                            .help("Project ID to look for merge request in. Defaults to attached Project ID.")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .validator(validator::check_u64)
+                    )
+                    .after_help(DRAFT_AFTER_HELP)
+            )
             .subcommand(
                 clap::SubCommand::with_name("wip")
-                    .about("Sets WIP status of merge request")
-                    .visible_alias("draft")
+                    .about("Sets draft status of merge request")
                     .setting(clap::AppSettings::ColoredHelp)
                     .group(ArgGroup::with_name("toggle")
                         .required(true)
@@ -476,13 +794,13 @@ NB: The current implementation requires that the GitLab-hosted git remote is cal
                     .arg(
                         clap::Arg::with_name("off")
                             .long("off")
-                            .help("Turns off WIP")
+                            .help("Marks as ready")
                             .group("toggle")
                     )
                     .arg(
                         clap::Arg::with_name("on")
                             .long("on")
-                            .help("Turns on WIP")
+                            .help("Marks as draft")
                             .group("toggle")
                     )
                     .arg(
@@ -494,6 +812,7 @@ NB: The current implementation requires that the GitLab-hosted git remote is cal
                             .takes_value(true)
                             .validator(validator::check_u64)
                     )
+                    .after_help(DRAFT_AFTER_HELP)
             )
             .subcommand(
                 clap::SubCommand::with_name("assign")
@@ -525,6 +844,40 @@ NB: The current implementation requires that the GitLab-hosted git remote is cal
                             .validator(validator::check_u64)
                     )
             )
+            .subcommand(
+                clap::SubCommand::with_name("review-request")
+                    .about("Requests a review of a merge request")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("id")
+                            .help("Merge request ID")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                    )
+                    .arg(
+                        clap::Arg::with_name("usernames")
+                            .help("Reviewer username(s)")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .multiple(true)
+                            .require_delimiter(true)
+                            .required(true)
+                    )
+                    .arg(
+                        clap::Arg::with_name("project_id")
+                            .short("p")
+                            .long("project_id")
+                            .help("Project ID to look for merge request in. Defaults to attached Project ID.")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .validator(validator::check_u64)
+                    )
+                    .after_help(
+"The given users are added to any reviewers already requested on the merge request. To replace \
+or remove reviewers, use `git lab mr edit --reviewers`."
+                    ),
+            )
             .subcommand(
                 clap::SubCommand::with_name("unlock")
                     .about("Unlocks a merge request")
@@ -573,6 +926,28 @@ NB: The current implementation requires that the GitLab-hosted git remote is cal
                             .short("S")
                             .help("Skip CI on rebase")
                     )
+                    .arg(
+                        clap::Arg::with_name("timeout")
+                            .long("timeout")
+                            .short("t")
+                            .help("Seconds to wait for the rebase to finish")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .default_value("120")
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("local")
+                            .long("local")
+                            .short("l")
+                            .help("Rebase the local checkout of the source branch to match")
+                    )
+                    .after_help(
+"Waits for GitLab to finish the rebase, and exits with an error if it fails, for example because \
+of conflicts, or doesn't finish in time. Servers older than GitLab 13.8 don't report whether a \
+rebase is in progress, so there a rebase which changes nothing only ends with the timeout. With \
+`--local`, the local source branch is then updated with `git pull --rebase`, if it is checked out.",
+                    ),
             )
             .subcommand(
                 clap::SubCommand::with_name("merge")
@@ -605,6 +980,73 @@ NB: The current implementation requires that the GitLab-hosted git remote is cal
                             .short("D")
                             .help("Don't delete source branch")
                     )
+                    .arg(
+                        clap::Arg::with_name("squash")
+                            .long("squash")
+                            .short("q")
+                            .help("Squash commits when merging")
+                    )
+                    .arg(
+                        clap::Arg::with_name("message")
+                            .long("message")
+                            .short("m")
+                            .help("Merge commit message")
+                            .takes_value(true)
+                            .empty_values(false)
+                    )
+                    .arg(
+                        clap::Arg::with_name("squash_message")
+                            .long("squash_message")
+                            .visible_alias("squash-message")
+                            .help("Squash commit message")
+                            .takes_value(true)
+                            .empty_values(false)
+                    )
+                    .arg(
+                        clap::Arg::with_name("edit")
+                            .long("edit")
+                            .short("e")
+                            .help("Edit the commit message(s) in $EDITOR")
+                    )
+                    .arg(
+                        clap::Arg::with_name("sha")
+                            .long("sha")
+                            .help("Only merge if the head of the source branch is at this commit")
+                            .takes_value(true)
+                            .empty_values(false)
+                    )
+                    .arg(
+                        clap::Arg::with_name("clean_up")
+                            .long("clean_up")
+                            .visible_alias("clean-up")
+                            .short("c")
+                            .help("Check out the default branch and delete the merged local branch")
+                            .conflicts_with("merge_when_pipeline_succeeds")
+                    )
+                    .arg(
+                        clap::Arg::with_name("cancel_auto")
+                            .long("cancel_auto")
+                            .visible_alias("cancel-auto")
+                            .help("Cancel merging when pipeline succeeds")
+                            .conflicts_with_all(&[
+                                "merge_when_pipeline_succeeds",
+                                "dont_del_source_branch",
+                                "squash",
+                                "message",
+                                "squash_message",
+                                "edit",
+                                "sha",
+                                "clean_up",
+                            ])
+                    )
+                    .after_help(
+"With `--edit`, the merge commit message, and the squash commit message if squashing, are opened \
+in $EDITOR, starting from those passed or else GitLab's defaults. \
+\
+With `--clean_up`, once merged, the local repo is switched to the project's default branch, which \
+is brought up to date, and the local source branch is deleted provided all of its commits were \
+merged. This can't be combined with `-M`, as the merge happens later.",
+                    )
             )
             .subcommand(
                 clap::SubCommand::with_name("unapprove")
@@ -649,6 +1091,46 @@ NB: The current implementation requires that the GitLab-hosted git remote is cal
                             .takes_value(true)
                             .validator(validator::check_u64)
                     )
+                    .arg(
+                        clap::Arg::with_name("sha")
+                            .long("sha")
+                            .help("Only approve if the head of the merge request is at this commit SHA")
+                            .empty_values(false)
+                            .takes_value(true)
+                    )
+                    .arg(
+                        clap::Arg::with_name("password")
+                            .long("password")
+                            .short("P")
+                            .help("Prompt for the user's password, for projects that require it to approve")
+                            .takes_value(false)
+                    )
+            )
+            .subcommand(
+                clap::SubCommand::with_name("approvals")
+                    .about("Shows the approval state of a merge request")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("id")
+                            .help("Merge request ID")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("project_id")
+                            .short("p")
+                            .long("project_id")
+                            .help("Project ID to look for merge request in. Defaults to attached Project ID.")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .validator(validator::check_u64)
+                    )
+                    .after_help(
+"Shows how many approvals the merge request needs, who has approved it and, where the GitLab \
+edition supports approval rules, which of the rules are satisfied.",
+                    ),
             )
             .subcommand(
                 clap::SubCommand::with_name("lock")
@@ -694,6 +1176,45 @@ NB: The current implementation requires that the GitLab-hosted git remote is cal
                             .takes_value(true)
                             .validator(validator::check_u64)
                     )
+                    .arg(
+                        clap::Arg::with_name("remote")
+                            .short("r")
+                            .long("remote")
+                            .help("Git remote to fetch the merge request from. Defaults to the remote of the project it targets")
+                            .empty_values(false)
+                            .takes_value(true)
+                    )
+                    .arg(
+                        clap::Arg::with_name("branch")
+                            .short("b")
+                            .long("branch")
+                            .help("Local branch to check out to. Defaults to the merge request's source branch")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .validator(validator::check_branch_name)
+                    )
+                    .arg(
+                        clap::Arg::with_name("detach")
+                            .short("d")
+                            .long("detach")
+                            .help("Check out the merge request without a local branch")
+                            .conflicts_with("branch")
+                    )
+                    .arg(
+                        clap::Arg::with_name("force")
+                            .short("f")
+                            .long("force")
+                            .help("Reset the local branch to the merge request, even if it has diverged")
+                            .conflicts_with("detach")
+                    )
+                    .after_help(
+"The merge request is fetched from the project it targets, using the `refs/merge-requests/<id>/head` \
+ref GitLab keeps there, so merge requests from forks can be checked out too. \
+\
+If the local branch already exists, it is fast-forwarded to the merge request, or checked out as it \
+is if it is ahead. If it has diverged, it is left alone unless `--force` is given. Local changes \
+which would be overwritten stop the checkout, as with `git checkout`."
+                    ),
             )
             .subcommand(
                 clap::SubCommand::with_name("reopen")
@@ -739,6 +1260,40 @@ NB: The current implementation requires that the GitLab-hosted git remote is cal
                             .validator(validator::check_u64)
                     )
             )
+            .subcommand(time_tracking::gen_time_clap_command(TimeTrackable::MergeRequest))
+            .subcommand(
+                clap::SubCommand::with_name("stack")
+                    .about("Shows a stack of merge requests, each targeting the source branch of the one before")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("id")
+                            .help("Merge request ID in the stack. Defaults to the one from the current branch.")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("project_id")
+                            .short("p")
+                            .long("project_id")
+                            .help("Project ID to look for merge requests in. Defaults to attached Project ID.")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("retarget")
+                            .long("retarget")
+                            .short("r")
+                            .help("Retarget the merge requests stacked on a merged one to the branch it was merged into")
+                    )
+                    .after_help(
+"Stacked merge requests are created with `git lab mr create --base <id>`. Once the merge request at \
+the bottom of a stack is merged, those stacked on it still target its source branch, so use \
+`--retarget` to point them at the branch it was merged into instead. The current merge request is \
+marked with `*`.",
+                    ),
+            )
             .subcommand(
                 clap::SubCommand::with_name("show")
                     .about("Shows merge request information in the terminal")
@@ -805,18 +1360,25 @@ try `xdg-open(1)`.",
         let gitlabclient = gitlab::new(&config).context("Could not create GitLab client connection.")?;
 
         match args.subcommand() {
+            ("approvals", Some(a)) => approvals::approvals_mr_cmd(a.clone(), config, *gitlabclient)?,
             ("approve", Some(a)) => approve::approve_mr_cmd(a.clone(), config, *gitlabclient)?,
             ("assign", Some(a)) => quick_edit::quick_edit_mr_cmd(a.clone(), ShortCmd::Assign, config, *gitlabclient)?,
             ("checkout", Some(a)) => checkout::checkout_merge_request_cmd(a.clone(), config, *gitlabclient)?,
             ("close", Some(a)) => quick_edit::quick_edit_mr_cmd(a.clone(), ShortCmd::Close, config, *gitlabclient)?,
+            ("draft", Some(a)) => quick_edit::quick_edit_mr_cmd(a.clone(), ShortCmd::Draft, config, *gitlabclient)?,
             ("create", Some(a)) => create::create_merge_request_cmd(a.clone(), config, *gitlabclient)?,
+            ("edit", Some(a)) => edit::edit_merge_request_cmd(a.clone(), config, *gitlabclient)?,
             ("list", Some(a)) => list::list_mrs_cmd(a.clone(), config, *gitlabclient)?,
             ("lock", Some(a)) => quick_edit::quick_edit_mr_cmd(a.clone(), ShortCmd::Lock, config, *gitlabclient)?,
             ("merge", Some(a)) => merge::merge_mr_cmd(a.clone(), config, *gitlabclient)?,
             ("open", Some(a)) => open::open_merge_request_cmd(a.clone(), config, *gitlabclient)?,
+            ("ready", Some(a)) => quick_edit::quick_edit_mr_cmd(a.clone(), ShortCmd::Ready, config, *gitlabclient)?,
             ("reopen", Some(a)) => quick_edit::quick_edit_mr_cmd(a.clone(), ShortCmd::Reopen, config, *gitlabclient)?,
             ("rebase", Some(a)) => rebase::rebase_mr_cmd(a.clone(), config, *gitlabclient)?,
+            ("review-request", Some(a)) => review_request::review_request_mr_cmd(a.clone(), config, *gitlabclient)?,
             ("show", Some(a)) => show::show_mr_cmd(a.clone(), config, *gitlabclient)?,
+            ("stack", Some(a)) => stack::stack_mr_cmd(a.clone(), config, *gitlabclient)?,
+            ("time", Some(a)) => time_tracking::time_cmd(a.clone(), TimeTrackable::MergeRequest, config, *gitlabclient)?,
             ("unapprove", Some(a)) => unapprove::unapprove_mr_cmd(a.clone(), config, *gitlabclient)?,
             ("unlock", Some(a)) => quick_edit::quick_edit_mr_cmd(a.clone(), ShortCmd::Unlock, config, *gitlabclient)?,
             ("wip", Some(a)) => quick_edit::quick_edit_mr_cmd(a.clone(), ShortCmd::Wip, config, *gitlabclient)?,
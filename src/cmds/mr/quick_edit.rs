@@ -8,13 +8,35 @@ use crate::gitlab::MergeRequest as GLMergeRequest;
 use crate::utils;
 use crate::cmds::mr::ShortCmd;
 
-fn strip_wip(s: String) -> String {
-    for prefix in ["[WIP]","WIP:", "Draft:", "[Draft]", "(Draft)"].iter() {
-        if s.starts_with(prefix) {
-            return s.strip_prefix(prefix).unwrap().to_string();
-        }
+/// The title prefix used to mark merge requests as drafts, unless set with `gitlab.draftprefix`
+const DEFAULT_DRAFT_PREFIX: &str = "Draft:";
+
+/// Title prefixes which GitLab recognises as marking a merge request as a draft, in lower case
+const DRAFT_PREFIXES: [&str; 5] = ["[draft]", "(draft)", "draft:", "[wip]", "wip:"];
+
+fn draft_prefix(config: &config::Config) -> &str {
+    config.draft_prefix.as_deref().unwrap_or(DEFAULT_DRAFT_PREFIX)
+}
+
+/// Prefix a title to mark the merge request as a draft
+pub fn draft_title(title: &str, config: &config::Config) -> String {
+    format!("{} {}", draft_prefix(config).trim_end(), title)
+}
+
+/// Strip any draft prefixes, including the configured one, from a title
+pub fn strip_draft(title: &str, config: &config::Config) -> String {
+    let configured = draft_prefix(config).trim();
+    let mut s = title.trim_start();
+
+    while let Some(prefix) = DRAFT_PREFIXES
+        .iter()
+        .copied()
+        .chain(std::iter::once(configured))
+        .find(|p| !p.is_empty() && matches!(s.get(..p.len()), Some(h) if h.eq_ignore_ascii_case(p)))
+    {
+        s = s[prefix.len()..].trim_start();
     }
-    s
+    s.to_string()
 }
 
 pub fn quick_edit_mr_cmd(
@@ -40,23 +62,24 @@ pub fn quick_edit_mr_cmd(
             let assign_ids = utils::map_user_ids_from_names(&config.members, args.values_of("usernames").unwrap())?;
             m.assignees(assign_ids.into_iter())
         }
-        ShortCmd::Wip => {
+        ShortCmd::Wip | ShortCmd::Draft | ShortCmd::Ready => {
             let mut p = GLMergeRequest::builder();
             let endpoint = generate_basic_mr_builder(&args, "id", &config, &mut p)?;
             let mr: MergeRequest = endpoint
                 .query(&gitlabclient)
                 .context("Failed to find merge request")?;
 
-            match (mr.work_in_progress, args.is_present("on"), args.is_present("off")) {
-                (true, _, true) => {
-                    // turn off WIP,
-                    m.title(strip_wip(mr.title))
-                }
-                (false, true, _) => {
-                    // turn on WIP,
-                    m.title("WIP: ".to_string() + &mr.title)
-                }
-                _ => m.title(mr.title) // do nothing,
+            let draft = match shortcmd {
+                ShortCmd::Draft => !args.is_present("off"),
+                ShortCmd::Ready => false,
+                _ => args.is_present("on"),
+            };
+
+            // GitLab has no API to set the draft state directly, only through the title
+            match (mr.is_draft(), draft) {
+                (true, false) => m.title(strip_draft(&mr.title, &config)),
+                (false, true) => m.title(draft_title(&mr.title, &config)),
+                _ => m.title(mr.title), // do nothing,
             }
         }
     };
@@ -74,3 +97,60 @@ pub fn quick_edit_mr_cmd(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod mr_quick_edit_unit_tests {
+    use rstest::*;
+
+    use super::*;
+
+    fn config(draft_prefix: Option<&str>) -> config::Config {
+        config::Config {
+            token: None,
+            host: None,
+            tls: None,
+            format: None,
+            repo_path: None,
+            path_with_namespace: None,
+            user_config_type: None,
+            projectid: None,
+            defaultbranch: None,
+            remote: None,
+            labels: Vec::new(),
+            members: Vec::new(),
+            fork_project_id: None,
+            fork_remote: None,
+            draft_prefix: draft_prefix.map(|p| p.to_string()),
+        }
+    }
+
+    #[rstest(
+        title, prefix, expected,
+        case("Draft: Fix widget", None, "Fix widget"),
+        case("draft:Fix widget", None, "Fix widget"),
+        case("[Draft] Fix widget", None, "Fix widget"),
+        case("(Draft) Fix widget", None, "Fix widget"),
+        case("WIP: Fix widget", None, "Fix widget"),
+        case("[WIP] Draft: Fix widget", None, "Fix widget"),
+        case("Fix widget", None, "Fix widget"),
+        case("Drafting widget", None, "Drafting widget"),
+        case("DRAFT - Fix widget", Some("DRAFT -"), "Fix widget"),
+    )]
+    fn test_strip_draft(title: &str, prefix: Option<&str>, expected: &str) {
+        let config = config(prefix);
+
+        assert_eq!(strip_draft(title, &config), expected);
+    }
+
+    #[rstest(
+        prefix, expected,
+        case(None, "Draft: Fix widget"),
+        case(Some("[Draft]"), "[Draft] Fix widget"),
+        case(Some("WIP: "), "WIP: Fix widget"),
+    )]
+    fn test_draft_title(prefix: Option<&str>, expected: &str) {
+        let config = config(prefix);
+
+        assert_eq!(draft_title("Fix widget", &config), expected);
+    }
+}
@@ -1,10 +1,77 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
 use anyhow::{anyhow, Context, Result};
 use clap::value_t_or_exit;
 
+use crate::cmds::mr::{get_source_remote, MergeRequest};
 use crate::config;
+use crate::gitlab::MergeRequest as GLMergeRequest;
 use crate::gitlab::{api, Client, RebaseMergeRequest, Query};
 use crate::utils;
 
+/// How often to check whether the rebase has finished
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, PartialEq)]
+enum RebaseOutcome {
+    InProgress,
+    Rebased,
+    UpToDate,
+    Failed(String),
+    /// Unchanged, with the merge error from before the rebase, so it may have failed again or not
+    /// have run at all
+    Unknown(String),
+}
+
+/// Work out how the rebase went from the merge request's state and its head commit and merge error
+/// before the rebase. A new head commit is taken as success whatever the merge error says. A merge
+/// error counts as a failure if it is new, or if the rebase was seen running, as one which fails
+/// again leaves the same error as before. Servers which don't report whether a rebase is in progress
+/// are polled until the head commit changes.
+fn rebase_outcome(
+    in_progress: Option<bool>,
+    seen_in_progress: bool,
+    sha_before: Option<&str>,
+    sha_after: Option<&str>,
+    error_before: Option<&str>,
+    error_after: Option<&str>,
+) -> RebaseOutcome {
+    match (in_progress, sha_before != sha_after, error_after) {
+        (Some(true), _, _) => RebaseOutcome::InProgress,
+        (_, true, _) => RebaseOutcome::Rebased,
+        (_, false, Some(e)) if seen_in_progress || error_after != error_before => RebaseOutcome::Failed(e.to_string()),
+        (None, false, _) => RebaseOutcome::InProgress,
+        (Some(false), false, Some(e)) => RebaseOutcome::Unknown(e.to_string()),
+        (Some(false), false, None) => RebaseOutcome::UpToDate,
+    }
+}
+
+fn get_mr(project_id: u64, mr_id: u64, gitlabclient: &Client) -> Result<MergeRequest> {
+    GLMergeRequest::builder()
+        .project(project_id)
+        .merge_request(mr_id)
+        .include_rebase_in_progress(true)
+        .build()
+        .map_err(|e| anyhow!("Could not construct query for this merge request.\n {}", e))?
+        .query(gitlabclient)
+        .context("Failed to find merge request")
+}
+
+/// Bring the local source branch up to date with the rebased one, if it's checked out
+fn rebase_local_checkout(config: &config::Config, mr: &MergeRequest) -> Result<()> {
+    let repo_path = config.repo_path.as_ref()
+        .ok_or_else(|| anyhow!("Local repo not found. Are you in the correct directory?"))?;
+
+    if utils::get_current_local_branch_name(repo_path).ok().as_ref() != Some(&mr.source_branch) {
+        println!("Not rebasing the local checkout, as {} is not checked out", mr.source_branch);
+        return Ok(())
+    }
+
+    let remote = get_source_remote(None, config, mr.source_project_id, mr.target_project_id);
+    utils::run_git(&["pull", "--rebase", &remote, &mr.source_branch])
+}
+
 pub fn rebase_mr_cmd(
     args: clap::ArgMatches,
     config: config::Config,
@@ -22,6 +89,8 @@ pub fn rebase_mr_cmd(
         m.skip_ci(true);
     }
 
+    let timeout = Duration::from_secs(value_t_or_exit!(args, "timeout", u64));
+
     let endpoint = m
         .build()
         .map_err(|e| anyhow!("Could not construct edit query.\n{}", e))?;
@@ -29,9 +98,104 @@ pub fn rebase_mr_cmd(
     debug!("args: {:#?}", args);
     debug!("endpoint: {:#?}", endpoint);
 
+    let before = get_mr(project_id, mr_id, &gitlabclient)?;
+
     api::ignore(endpoint)
         .query(&gitlabclient)
         .context("Failed to update merge request")?;
 
-    Ok(())
+    println!("Rebasing !{}...", mr_id);
+
+    let start = Instant::now();
+    let mut seen_in_progress = false;
+    loop {
+        let mr = get_mr(project_id, mr_id, &gitlabclient)?;
+
+        debug!("rebase_in_progress: {:?}, merge_error: {:?}", mr.rebase_in_progress, mr.merge_error);
+
+        seen_in_progress |= mr.rebase_in_progress == Some(true);
+
+        match rebase_outcome(
+            mr.rebase_in_progress,
+            seen_in_progress,
+            before.sha.as_deref(),
+            mr.sha.as_deref(),
+            before.merge_error.as_deref(),
+            mr.merge_error.as_deref(),
+        ) {
+            RebaseOutcome::InProgress if start.elapsed() >= timeout => {
+                return Err(match mr.rebase_in_progress {
+                    Some(_) => anyhow!(
+                        "Rebase of !{} still in progress after {} seconds - check on it with `git lab mr show {}`",
+                        mr_id, timeout.as_secs(), mr_id
+                    ),
+                    // the server doesn't say, and the branch may simply have been up to date
+                    None => anyhow!(
+                        "Rebase of !{} did not change it within {} seconds, and the server doesn't report \
+                        whether it is still in progress - check on it with `git lab mr show {}`",
+                        mr_id, timeout.as_secs(), mr_id
+                    ),
+                })
+            },
+            RebaseOutcome::InProgress => thread::sleep(POLL_INTERVAL),
+            RebaseOutcome::Rebased => {
+                println!("Rebased {} onto {}", mr.source_branch, mr.target_branch);
+                if args.is_present("local") {
+                    rebase_local_checkout(&config, &mr)?;
+                }
+                return Ok(())
+            },
+            RebaseOutcome::UpToDate => {
+                println!("{} is already up to date with {}", mr.source_branch, mr.target_branch);
+                if args.is_present("local") {
+                    rebase_local_checkout(&config, &mr)?;
+                }
+                return Ok(())
+            },
+            RebaseOutcome::Failed(e) => return Err(anyhow!("Rebase of !{} failed: {}", mr_id, e)),
+            RebaseOutcome::Unknown(e) => return Err(anyhow!(
+                "Rebase of !{} left it unchanged, with the error from before: {} - it may have failed again, \
+                so check on it with `git lab mr show {}`",
+                mr_id, e, mr_id
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod mr_rebase_unit_tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest(
+        in_progress, seen, sha_after, error_before, error_after, expected,
+        case(Some(true), true, "a", None, None, RebaseOutcome::InProgress),
+        case(Some(true), true, "a", None, Some("Rebase failed"), RebaseOutcome::InProgress),
+        case(Some(false), true, "b", None, None, RebaseOutcome::Rebased),
+        case(Some(false), true, "b", None, Some("Rebase failed"), RebaseOutcome::Rebased),
+        case(Some(false), true, "a", None, None, RebaseOutcome::UpToDate),
+        case(Some(false), false, "a", None, None, RebaseOutcome::UpToDate),
+        case(Some(false), false, "a", None, Some("Rebase failed"), RebaseOutcome::Failed("Rebase failed".to_string())),
+        case(Some(false), true, "a", Some("Rebase failed"), Some("Rebase failed"), RebaseOutcome::Failed("Rebase failed".to_string())),
+        case(Some(false), false, "a", Some("Rebase failed"), Some("Rebase failed"), RebaseOutcome::Unknown("Rebase failed".to_string())),
+        case(Some(false), false, "a", Some("Old error"), Some("Rebase failed"), RebaseOutcome::Failed("Rebase failed".to_string())),
+        case(None, false, "a", None, None, RebaseOutcome::InProgress),
+        case(None, false, "a", Some("Rebase failed"), Some("Rebase failed"), RebaseOutcome::InProgress),
+        case(None, false, "b", None, None, RebaseOutcome::Rebased),
+        case(None, false, "a", None, Some("Rebase failed"), RebaseOutcome::Failed("Rebase failed".to_string())),
+    )]
+    fn test_rebase_outcome(
+        in_progress: Option<bool>,
+        seen: bool,
+        sha_after: &str,
+        error_before: Option<&str>,
+        error_after: Option<&str>,
+        expected: RebaseOutcome,
+    ) {
+        assert_eq!(
+            rebase_outcome(in_progress, seen, Some("a"), Some(sha_after), error_before, error_after),
+            expected
+        );
+    }
 }
@@ -0,0 +1,44 @@
+use anyhow::{anyhow, Context, Result};
+use clap::value_t_or_exit;
+
+use crate::cmds::mr::{generate_basic_mr_builder, MergeRequest};
+use crate::config;
+use crate::gitlab::MergeRequest as GLMergeRequest;
+use crate::gitlab::{api, Client, Query, UpdateMergeRequest};
+use crate::utils;
+
+pub fn review_request_mr_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+    let mr_id = value_t_or_exit!(args, "id", u64);
+
+    let new_ids = utils::map_user_ids_from_names(&config.members, args.values_of("usernames").unwrap())?;
+
+    // reviewers are replaced wholesale by the server, so keep any already requested
+    let mut mb = GLMergeRequest::builder();
+    let current_endpoint = generate_basic_mr_builder(&args, "id", &config, &mut mb)?;
+    let mr: MergeRequest = current_endpoint
+        .query(&gitlabclient)
+        .context("Failed to find merge request")?;
+
+    let reviewer_ids = mr.reviewers
+        .iter()
+        .flatten()
+        .filter_map(|r| r["id"].as_u64())
+        .chain(new_ids);
+
+    let endpoint = UpdateMergeRequest::builder()
+        .project(project_id)
+        .merge_request(mr_id)
+        .reviewer_ids(reviewer_ids)
+        .build()
+        .map_err(|e| anyhow!("Could not construct review request query.\n{}", e))?;
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    api::ignore(endpoint)
+        .query(&gitlabclient)
+        .context("Failed to request review of merge request")?;
+
+    Ok(())
+}
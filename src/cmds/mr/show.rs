@@ -24,6 +24,7 @@ fn print_mr(m: MergeRequest) {
     let dot = format!("{}", "•".dimmed());
     let comments = format!("{}", "comments".dimmed());
     let assignee_str = format!("{}", "assigned".italic().blue().bold());
+    let reviewer_str = format!("{}", "reviewers".italic().blue().bold());
     let updated = format!("{}", "updated".dimmed());
     let m_status = match m.merge_status.as_str() {
         "can_be_merged" if m.state == "opened" => "can be merged".to_string().italic().bold(),
@@ -88,6 +89,23 @@ fn print_mr(m: MergeRequest) {
             );
     }
 
+    let reviewer_names = m
+        .reviewers
+        .unwrap_or_default()
+        .iter()
+        .map(|e| e["username"].as_str().unwrap().to_string())
+        .collect::<Vec<String>>();
+
+    if !reviewer_names.is_empty() {
+
+        println!(
+            "{} {} {}",
+            reviewer_str,
+            dot,
+            reviewer_names.join(&format!(" {} ", dot)).dimmed(),
+            );
+    }
+
     lazy_static! {
         static ref WHITESPACE_RE: Regex = Regex::new(r"\s").unwrap();
     }
@@ -0,0 +1,262 @@
+//! Stacked merge requests, where each targets the source branch of the one before it, rather than
+//! the default branch.
+use anyhow::{anyhow, Context, Result};
+use clap::value_t;
+use serde::Deserialize;
+
+use crate::config;
+use crate::gitlab::{api, Client, MergeRequestState, MergeRequests, Query, UpdateMergeRequest};
+use crate::utils;
+
+#[derive(Debug, Deserialize)]
+struct StackMr {
+    iid: u64,
+    title: String,
+    source_branch: String,
+    target_branch: String,
+    source_project_id: u64,
+}
+
+/// Follow the target branches down from a merge request to the bottom of its stack, the one whose
+/// target branch isn't the source of another
+fn stack_bottom(mrs: &[StackMr], iid: u64) -> Option<&StackMr> {
+    let mut bottom = mrs.iter().find(|m| m.iid == iid)?;
+
+    // bounded, in case the merge requests target each other in a loop
+    for _ in 0..mrs.len() {
+        match mrs.iter().find(|m| m.source_branch == bottom.target_branch) {
+            Some(parent) => bottom = parent,
+            None => break,
+        }
+    }
+
+    Some(bottom)
+}
+
+/// Lines showing a merge request, followed by those stacked on it
+fn stack_lines(mrs: &[StackMr], mr: &StackMr, current: u64, depth: usize, lines: &mut Vec<String>) {
+    if depth > mrs.len() {
+        return
+    }
+
+    lines.push(format!(
+        "{} {}!{} {} ({})",
+        if mr.iid == current { "*" } else { " " },
+        "  ".repeat(depth + 1),
+        mr.iid,
+        mr.title,
+        mr.source_branch,
+    ));
+
+    let mut children = mrs.iter().filter(|m| m.target_branch == mr.source_branch).collect::<Vec<&StackMr>>();
+    children.sort_by_key(|m| m.iid);
+
+    for child in children {
+        stack_lines(mrs, child, current, depth + 1, lines);
+    }
+}
+
+/// Follow a branch through the merge requests merged from it to the branch its changes ended up
+/// in, if it has been merged
+fn resolve_target<F>(branch: &str, mut merged_into: F) -> Result<Option<String>>
+where
+    F: FnMut(&str) -> Result<Option<String>>,
+{
+    let mut target = branch.to_string();
+    let mut seen = vec![target.clone()];
+
+    while let Some(t) = merged_into(&target)? {
+        if seen.contains(&t) {
+            break
+        }
+        seen.push(t.clone());
+        target = t;
+    }
+
+    Ok(if target == branch { None } else { Some(target) })
+}
+
+/// Return the target branch of the latest merge request merged from a branch, if any
+fn merged_into(project_id: u64, branch: &str, gitlabclient: &Client) -> Result<Option<String>> {
+    let endpoint = MergeRequests::builder()
+        .project(project_id)
+        .state(MergeRequestState::Merged)
+        .source_branch(branch)
+        .build()
+        .map_err(|e| anyhow!("Could not construct merge requests query.\n {}", e))?;
+
+    let mrs: Vec<StackMr> = api::paged(endpoint, api::Pagination::Limit(1))
+        .query(gitlabclient)
+        .context("Failed to query merged merge requests")?;
+
+    Ok(mrs.into_iter().next().map(|m| m.target_branch))
+}
+
+fn open_mrs(project_id: u64, gitlabclient: &Client) -> Result<Vec<StackMr>> {
+    let endpoint = MergeRequests::builder()
+        .project(project_id)
+        .state(MergeRequestState::Opened)
+        .build()
+        .map_err(|e| anyhow!("Could not construct merge requests query.\n {}", e))?;
+
+    let mrs: Vec<StackMr> = api::paged(endpoint, api::Pagination::All)
+        .query(gitlabclient)
+        .context("Failed to query open merge requests")?;
+
+    // merge requests from forks can't be stacked on, and their branch names could clash
+    Ok(mrs.into_iter().filter(|m| m.source_project_id == project_id).collect())
+}
+
+fn retarget_mr(project_id: u64, mr: &StackMr, target: &str, gitlabclient: &Client) -> Result<()> {
+    let endpoint = UpdateMergeRequest::builder()
+        .project(project_id)
+        .merge_request(mr.iid)
+        .target_branch(target)
+        .build()
+        .map_err(|e| anyhow!("Could not construct API call to retarget merge request.\n {}", e))?;
+
+    api::ignore(endpoint)
+        .query(gitlabclient)
+        .with_context(|| format!("Failed to retarget merge request !{}", mr.iid))?;
+
+    println!("Retargeted !{} from {} to {}", mr.iid, mr.target_branch, target);
+    Ok(())
+}
+
+pub fn stack_mr_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+
+    debug!("args: {:#?}", args);
+
+    let mut mrs = open_mrs(project_id, &gitlabclient)?;
+
+    let iid = match value_t!(args, "id", u64).ok() {
+        Some(i) => i,
+        None => {
+            let repo_path = config.repo_path.as_ref()
+                .ok_or_else(|| anyhow!("Local repo not found. Pass a merge request ID or run this in a local repo."))?;
+            let branch = utils::get_current_local_branch_name(repo_path)?;
+            mrs.iter()
+                .find(|m| m.source_branch == branch)
+                .map(|m| m.iid)
+                .ok_or_else(|| anyhow!("No open merge request from the current branch {}", branch))?
+        },
+    };
+
+    let root = stack_bottom(&mrs, iid)
+        .map(|m| m.target_branch.clone())
+        .ok_or_else(|| anyhow!("Merge request !{} is not open", iid))?;
+
+    debug!("stack root: {}", root);
+
+    let merged_target = if Some(&root) != config.defaultbranch.as_ref() {
+        resolve_target(&root, |b| merged_into(project_id, b, &gitlabclient))?
+    } else {
+        None
+    };
+
+    let root = match merged_target {
+        Some(target) if args.is_present("retarget") => {
+            for mr in mrs.iter_mut().filter(|m| m.target_branch == root) {
+                retarget_mr(project_id, mr, &target, &gitlabclient)?;
+                mr.target_branch = target.clone();
+            }
+            target
+        },
+        Some(target) => {
+            println!(
+                "{} has been merged into {} - retarget the merge requests stacked on it with `--retarget`",
+                root, target
+            );
+            root
+        },
+        None => root,
+    };
+
+    let mut lines = vec![format!("  {}", root)];
+    if let Some(bottom) = stack_bottom(&mrs, iid) {
+        stack_lines(&mrs, bottom, iid, 0, &mut lines);
+    }
+
+    for line in lines {
+        println!("{}", line);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod mr_stack_unit_tests {
+    use std::collections::HashMap;
+
+    use rstest::*;
+
+    use super::*;
+
+    fn mr(iid: u64, source: &str, target: &str) -> StackMr {
+        StackMr {
+            iid,
+            title: format!("MR {}", iid),
+            source_branch: source.to_string(),
+            target_branch: target.to_string(),
+            source_project_id: 1,
+        }
+    }
+
+    fn stack() -> Vec<StackMr> {
+        vec![
+            mr(1, "a", "main"),
+            mr(2, "b", "a"),
+            mr(3, "c", "b"),
+            mr(4, "d", "a"),
+            mr(5, "e", "main"),
+        ]
+    }
+
+    #[rstest(
+        iid, expected,
+        case(1, Some(1)),
+        case(3, Some(1)),
+        case(5, Some(5)),
+        case(6, None),
+    )]
+    fn test_stack_bottom(iid: u64, expected: Option<u64>) {
+        assert_eq!(stack_bottom(&stack(), iid).map(|m| m.iid), expected);
+    }
+
+    #[test]
+    fn test_stack_bottom_loop() {
+        let mrs = vec![mr(1, "a", "b"), mr(2, "b", "a")];
+        assert!(stack_bottom(&mrs, 1).is_some());
+    }
+
+    #[test]
+    fn test_stack_lines() {
+        let mrs = stack();
+        let mut lines = Vec::new();
+        stack_lines(&mrs, &mrs[0], 3, 0, &mut lines);
+
+        assert_eq!(lines, vec![
+            "    !1 MR 1 (a)",
+            "      !2 MR 2 (b)",
+            "*       !3 MR 3 (c)",
+            "      !4 MR 4 (d)",
+        ]);
+    }
+
+    #[rstest(
+        branch, expected,
+        case("a", Some("main")),
+        case("b", Some("main")),
+        case("main", None),
+        case("x", Some("y")),
+    )]
+    fn test_resolve_target(branch: &str, expected: Option<&str>) {
+        let merged: HashMap<&str, &str> = vec![("a", "main"), ("b", "a"), ("x", "y"), ("y", "x")]
+            .into_iter()
+            .collect();
+
+        let target = resolve_target(branch, |b| Ok(merged.get(b).map(|t| t.to_string()))).unwrap();
+        assert_eq!(target.as_deref(), expected);
+    }
+}
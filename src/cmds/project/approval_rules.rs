@@ -0,0 +1,181 @@
+//! Project-level merge request approval rules, managed with the `project approval-rules`
+//! subcommand.
+use anyhow::{anyhow, Context, Result};
+use clap::value_t_or_exit;
+use serde::Deserialize;
+
+use crate::config;
+use crate::config::OutputFormat;
+use crate::gitlab::{api, Client, CreateProjectApprovalRule, ProjectApprovalRules, ProtectedBranches, Query};
+use crate::utils;
+
+#[derive(Debug, Deserialize)]
+struct User {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Group {
+    full_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProtectedBranch {
+    id: u64,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApprovalRule {
+    name: String,
+    approvals_required: u64,
+    users: Vec<User>,
+    groups: Vec<Group>,
+    protected_branches: Vec<ProtectedBranch>,
+}
+
+fn print_approval_rules(rules: Vec<ApprovalRule>) {
+    if rules.is_empty() {
+        println!("No approval rules");
+        return
+    }
+
+    let width = rules.iter().map(|r| r.name.len()).max().unwrap_or(0);
+
+    for rule in rules {
+        let approvers = rule.users
+            .into_iter()
+            .map(|u| u.username)
+            .chain(rule.groups.into_iter().map(|g| g.full_path))
+            .collect::<Vec<String>>();
+
+        let branches = if rule.protected_branches.is_empty() {
+            "all branches".to_string()
+        } else {
+            rule.protected_branches.into_iter().map(|b| b.name).collect::<Vec<String>>().join(", ")
+        };
+
+        println!(
+            "{:<width$}  {} required  {}  {}",
+            rule.name,
+            rule.approvals_required,
+            branches,
+            if approvers.is_empty() { "-".to_string() } else { approvers.join(", ") },
+            width = width,
+        );
+    }
+}
+
+fn list_approval_rules_cmd(args: &clap::ArgMatches, config: &config::Config, gitlabclient: &Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(args, config)?;
+
+    let endpoint = ProjectApprovalRules::builder()
+        .project(project_id)
+        .build()
+        .map_err(|e| anyhow!("Could not construct approval rules query.\n {}", e))?;
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    match config.format {
+        Some(OutputFormat::JSON) => {
+            let raw_json = api::raw(endpoint)
+                .query(gitlabclient)
+                .context("Failed to query approval rules")?;
+
+            println!("{}", String::from_utf8(raw_json).unwrap());
+            Ok(())
+        }
+
+        Some(OutputFormat::Text) => {
+            let rules: Vec<ApprovalRule> = api::paged(endpoint, api::Pagination::All)
+                .query(gitlabclient)
+                .context("Failed to query approval rules")?;
+
+            print_approval_rules(rules);
+            Ok(())
+        }
+        _ => Err(anyhow!("Bad output format in config")),
+    }
+}
+
+/// Map protected branch names onto their IDs, which the API needs to scope a rule to them
+fn protected_branch_ids<'a>(
+    project_id: u64,
+    names: clap::Values<'a>,
+    gitlabclient: &Client,
+) -> Result<Vec<u64>> {
+    let endpoint = ProtectedBranches::builder()
+        .project(project_id)
+        .build()
+        .map_err(|e| anyhow!("Could not construct protected branches query.\n {}", e))?;
+
+    let protected: Vec<ProtectedBranch> = api::paged(endpoint, api::Pagination::All)
+        .query(gitlabclient)
+        .context("Failed to query protected branches")?;
+
+    names
+        .map(|n| protected
+            .iter()
+            .find(|b| b.name == n)
+            .map(|b| b.id)
+            .ok_or_else(|| anyhow!("Branch {} is not protected - protect it with `git lab branch protect`", n)))
+        .collect()
+}
+
+fn create_approval_rule_cmd(args: &clap::ArgMatches, config: &config::Config, gitlabclient: &Client) -> Result<()> {
+    let project_id = utils::get_proj_from_arg_or_conf(args, config)?;
+
+    let mut r = CreateProjectApprovalRule::builder();
+    r.project(project_id)
+        .name(args.value_of("name").unwrap())
+        .approvals_required(value_t_or_exit!(args, "approvals", u64));
+
+    if let Some(users) = args.values_of("users") {
+        r.user_ids(utils::map_user_ids_from_names(&config.members, users)?);
+    }
+
+    if let Some(groups) = args.values_of("groups") {
+        r.group_ids(groups.map(|g| g.parse::<u64>().unwrap()));
+    }
+
+    if let Some(branches) = args.values_of("branches") {
+        r.protected_branch_ids(protected_branch_ids(project_id, branches, gitlabclient)?);
+    }
+
+    let endpoint = r
+        .build()
+        .map_err(|e| anyhow!("Could not construct approval rule query.\n {}", e))?;
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    match config.format {
+        Some(OutputFormat::JSON) => {
+            let raw_json = api::raw(endpoint)
+                .query(gitlabclient)
+                .context("Failed to create approval rule")?;
+
+            println!("{}", String::from_utf8(raw_json).unwrap());
+            Ok(())
+        }
+
+        Some(OutputFormat::Text) => {
+            let rule: ApprovalRule = endpoint
+                .query(gitlabclient)
+                .context("Failed to create approval rule")?;
+
+            println!("Created approval rule {}", rule.name);
+            Ok(())
+        }
+        _ => Err(anyhow!("Bad output format in config")),
+    }
+}
+
+pub fn approval_rules_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    match args.subcommand() {
+        ("list", Some(a)) => list_approval_rules_cmd(a, &config, &gitlabclient),
+        ("create", Some(a)) => create_approval_rule_cmd(a, &config, &gitlabclient),
+        _ => unreachable!(),
+    }
+}
@@ -1,14 +1,15 @@
 //! This module implements attaching (ie associating) a project in GitLab to a local git repo.
 //!
-//! It does this by referring to the local git remote (which must be set) and looking it up on the
-//! GitLab server. If found, it will update and persist local repo-specific config to contain the
+//! It does this by referring to the local git remote(s) (which must be set) and looking them up on
+//! the GitLab server. If found, it will update and persist local repo-specific config to contain the
 //! GitLab project's ID so that other project-specific commands can use it.
+//!
+//! The remote to use can be passed explicitly or configured with `gitlab.remote`. Otherwise all
+//! remotes pointing at the configured GitLab host are considered, and where these are an
+//! upstream/fork pair the upstream project is attached and the fork recorded alongside it.
 use anyhow::{anyhow, Context, Result};
 use clap::value_t;
 use git2::Repository;
-use graphql_client::GraphQLQuery;
-use lazy_static::lazy_static;
-use regex::Regex;
 use serde::Deserialize;
 
 use crate::config;
@@ -19,111 +20,127 @@ use crate::gitlab::{api, Query};
 use crate::gitlab;
 use crate::utils;
 
-/// This maps to GitLab's two Project fields: `sshUrlToRepo` and `httpUrlToRepo`
-///
-/// Note that from a git perspective, server protocols under the `ssh` type in gitlab could either
-/// be prefixed `git@` or `ssh://`. We lump both of these types in as _SSH_ as GitLab does although
-/// technically, they are different protocols as discussed
-/// [here](https://git-scm.com/book/en/v2/Git-on-the-Server-The-Protocols).
-///
-/// Note also that HTTPS is (obviously) covered under the _HTTP_ protocol.
+/// A git remote of the local repo, and the GitLab project it was found to point at
 #[derive(Debug)]
-#[derive(PartialEq)]
-enum RemoteType {
-    HTTP,
-    SSH,
+struct RemoteProject {
+    remote: String,
+    project_id: u64,
+    forked_from_project_id: Option<u64>,
 }
 
-#[derive(GraphQLQuery)]
-#[graphql(
-    schema_path = "src/graphql/schema.json",
-    query_path = "src/graphql/projects_with_remotes.graphql",
-    response_derives = "Debug"
-)]
-struct ProjectsWithRemotes;
-
-/// Return the push url for the `origin` remote, if set.
-fn get_git_remote(config: &config::Config) -> Option<String> {
+/// Return the url for the named remote, if set.
+fn get_git_remote(config: &config::Config, name: &str) -> Option<String> {
     let repo = Repository::open(config.repo_path.as_ref()?).ok()?;
-    let origin = repo.find_remote("origin").ok()?;
-    let remote_str  = String::from(origin.url()?);
+    let remote = repo.find_remote(name).ok()?;
+    let remote_str  = String::from(remote.url()?);
     Some(remote_str)
 }
 
-/// Given a remote url, figure out what type it is, and what search term to find it with
-fn get_search_param_and_remote_type(url: &str) -> (RemoteType, String) {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"^(?P<remote>ssh|git|http)[^\s]+/(?P<search>[^\s]+)\.git$").unwrap();
-    }
+/// Return the `(name, url)` pairs of the remotes to try to attach with: the one passed or
+/// configured if any, otherwise those pointing at the GitLab host, falling back on `origin`.
+fn get_candidate_remotes(args: &clap::ArgMatches, config: &config::Config) -> Result<Vec<(String, String)>> {
+    let explicit = args.value_of("remote").map(|r| r.to_string()).or_else(|| config.remote.clone());
 
-    let caps = RE.captures(url).unwrap();
+    if let Some(name) = explicit {
+        let url = get_git_remote(config, &name)
+            .ok_or_else(|| anyhow!("Git remote '{}' not found. Set the remote or pass the project details explicitly", name))?;
+        return Ok(vec!((name, url)));
+    }
 
-    let r_type = match caps.name("remote").unwrap().as_str() {
-        "http" => RemoteType::HTTP,
-        "ssh" => RemoteType::SSH,
-        "git" => RemoteType::SSH,
-        _ => unreachable!(),
+    let remotes = match config.host.as_ref() {
+        Some(host) => utils::get_gitlab_remotes(config.repo_path.as_ref().unwrap(), host)?,
+        None => vec!(),
     };
 
-    (r_type, caps.name("search").unwrap().as_str().to_string())
+    if !remotes.is_empty() {
+        return Ok(remotes);
+    }
+
+    get_git_remote(config, "origin")
+        .map(|url| vec!(("origin".to_string(), url)))
+        .ok_or_else(|| anyhow!("No git remote pointing at the GitLab host, nor an 'origin' remote, found. \
+Set the remote or pass the project details explicitly"))
 }
 
-/// Get list of remote-pairs from server which match search string
-fn get_remotes_from_server(search_str: &str, gitlabclient: &gitlab::Client) -> Result<projects_with_remotes::ResponseData> {
-    let query_body = ProjectsWithRemotes::build_query(
-        projects_with_remotes::Variables {
-            search_str: search_str.to_string(),
-        }
-    );
+fn get_forked_from_project_id(project_id: u64, gitlabclient: &gitlab::Client) -> Result<Option<u64>> {
+    let mut project_builder  = GLProject::builder();
+    let endpoint = project_builder.project(project_id).build()
+        .map_err(|e| anyhow!("Could not fetch project from server.\n {}",e))?;
 
-    debug!("graphql search string: {}", search_str);
+    #[derive(Deserialize, Debug)]
+    struct ForkedFrom {
+        id: u64
+    }
 
-    let response = gitlabclient.graphql::<ProjectsWithRemotes>(&query_body)
-        .context("GraphQL error when looking for Projects with matching remotes")?;
+    #[derive(Deserialize, Debug)]
+    struct Project {
+        forked_from_project: Option<ForkedFrom>
+    }
 
-    Ok(response)
-}
+    let project: Project = endpoint
+        .query(gitlabclient)
+        .context("Failed to query project")?;
 
-/// Parse project id from `gid://gitlab/Project/<pid>` string
-fn parse_pid_from_gid(gid_str: &str) -> u64 {
-    let v: Vec<&str> = gid_str.rsplit('/').collect();
-    v[0].parse::<u64>().unwrap()
+    Ok(project.forked_from_project.map(|p| p.id))
 }
 
-/// Return the GitLab project ID
-fn find_project_id(r_type: RemoteType, url: &str, remotes: projects_with_remotes::ResponseData) -> Result<u64> {
-
-    let search_array = remotes.projects.unwrap().nodes.unwrap();
-
-    for item in search_array {
-
-        let item = item.unwrap();
+/// Pick the project to attach out of those found for the candidate remotes, along with the fork
+/// (if any) that should be recorded alongside it
+fn choose_remote_project(mut found: Vec<RemoteProject>) -> Result<(RemoteProject, Option<RemoteProject>)> {
+    if found.len() == 1 {
+        return Ok((found.remove(0), None));
+    }
 
-        let gl_remote = match r_type {
-            RemoteType::HTTP => item.http_url_to_repo.unwrap(),
-            RemoteType::SSH => item.ssh_url_to_repo.unwrap(),
+    // an upstream/fork pair: attach the upstream and remember the fork
+    let pair = found.iter().enumerate().find_map(|(f, fork)| {
+        let parent = fork.forked_from_project_id?;
+        found.iter().position(|u| u.project_id == parent).map(|u| (u, f))
+    });
+
+    if let Some((u, f)) = pair {
+        let (upstream, fork) = if u < f {
+            let fork = found.remove(f);
+            (found.remove(u), fork)
+        } else {
+            let upstream = found.remove(u);
+            (upstream, found.remove(f))
         };
+        return Ok((upstream, Some(fork)));
+    }
 
-        if gl_remote == url {
-            return Ok(parse_pid_from_gid(&item.id))
+    for name in &["upstream", "origin"] {
+        if let Some(i) = found.iter().position(|r| r.remote == *name) {
+            return Ok((found.remove(i), None));
         }
     }
 
-    Err(anyhow!("Counldn't find matching project ID"))
+    Err(anyhow!("Several git remotes point at GitLab projects ({}). Pick one with `--remote`",
+        found.iter().map(|r| r.remote.as_str()).collect::<Vec<&str>>().join(", ")))
 }
 
-/// Look up the project ID on the GitLab server from a git remote url.
+/// Look up the project ID on the GitLab server from a git remote url, using the project's full
+/// path within it.
 fn get_proj_id_by_remote(url: &str, gitlabclient: &gitlab::Client) -> Result<u64> {
     trace!("url: {:#?}", url);
 
-    let (r_type, search_str) = get_search_param_and_remote_type(url);
+    let remote: utils::RemoteUrl = url.parse()?;
 
-    let response = get_remotes_from_server(&search_str, gitlabclient)?;
-    trace!("response: {:#?}", response);
+    let mut project_builder  = GLProject::builder();
+    let endpoint = project_builder.project(remote.path.as_str()).build()
+        .map_err(|e| anyhow!("Could not fetch project from server.\n {}",e))?;
 
-    let p_id = find_project_id(r_type, url, response)?;
+    debug!("endpoint: {:#?}", endpoint);
 
-    Ok(p_id)
+    #[derive(Deserialize, Debug)]
+    struct Project {
+        id: u64
+    }
+
+    let project: Project = endpoint
+        .query(gitlabclient)
+        .with_context(|| format!("Failed to find project {}", remote.path))?;
+
+    Ok(project.id)
 }
 
 // Note that this function implements a workaround for a buggy Gitlab API. The include ancestors
@@ -239,27 +256,69 @@ pub fn attach_project_cmd(args: clap::ArgMatches, mut config: config::Config, gi
 
     debug!("config: {:#?}", &config);
 
-    let project_id = match (&get_git_remote(&config), &args) {
-        (Some(r), a) if !a.is_present("project_id") => {
-            get_proj_id_by_remote(r, &gitlabclient)
-                .with_context(|| format!("Could not look up GitLab project using 'origin' remote '{}'", r))
-                .context("Your GitLab server is probably not at a version with decent GraphQL support.")
-        },
-        (_, a) if a.is_present("project_id") => {
-            a.value_of("project_id").unwrap().parse::<u64>().map_err(|e| anyhow!(e))
-        },
-        (r, a) => {
-            trace!("remote_url: {:#?}", r);
-            trace!("args: {:#?}", a);
-            Err(anyhow!("Git remote 'origin' not found. Set the remote or pass the project details explicitly"))
+    let project_id = if args.is_present("project_id") {
+        if let Some(r) = args.value_of("remote") {
+            config.remote = Some(r.to_string());
+        }
+        args.value_of("project_id").unwrap().parse::<u64>().map_err(|e| anyhow!(e))?
+    } else {
+        let remotes = get_candidate_remotes(&args, &config)?;
+        trace!("candidate remotes: {:#?}", remotes);
+
+        let mut found = Vec::new();
+        let mut last_err = None;
+        for (name, url) in remotes {
+            match get_proj_id_by_remote(&url, &gitlabclient)
+                .with_context(|| format!("Could not look up GitLab project using '{}' remote '{}'", name, url))
+            {
+                Ok(project_id) => found.push(RemoteProject {
+                    remote: name,
+                    project_id,
+                    forked_from_project_id: get_forked_from_project_id(project_id, &gitlabclient)?,
+                }),
+                Err(e) => last_err = Some(e),
+            }
+        }
+
+        if found.is_empty() {
+            return Err(last_err.unwrap());
         }
-    }?;
 
+        debug!("remote projects: {:#?}", found);
+
+        let (upstream, fork) = choose_remote_project(found)?;
+
+        if let Some(f) = fork {
+            config.fork_project_id = Some(f.project_id);
+            config.fork_remote = Some(f.remote);
+        }
+        config.remote = Some(upstream.remote);
+        upstream.project_id
+    };
+
+    attach_project(
+        project_id,
+        value_t!(args, "max_labels", u64).unwrap(),
+        value_t!(args, "max_members", u64).unwrap(),
+        config,
+        &gitlabclient
+    )
+}
+
+/// Associate the local repo in `config.repo_path` with the given project, caching the project's
+/// metadata in the repo's git config.
+pub fn attach_project(
+    project_id: u64,
+    max_labels: u64,
+    max_members: u64,
+    mut config: config::Config,
+    gitlabclient: &gitlab::Client,
+) -> Result<()> {
     config.projectid = Some(project_id);
-    config.defaultbranch = get_project_defaultbranch(project_id, &gitlabclient).ok();
-    config.path_with_namespace = get_project_path_with_namespace(project_id, &gitlabclient).ok();
-    config.labels = get_project_labels(project_id, value_t!(args, "max_labels", u64).unwrap(), &gitlabclient)?;
-    config.members = get_project_members(project_id, value_t!(args, "max_members", u64).unwrap(), &gitlabclient)?;
+    config.defaultbranch = get_project_defaultbranch(project_id, gitlabclient).ok();
+    config.path_with_namespace = get_project_path_with_namespace(project_id, gitlabclient).ok();
+    config.labels = get_project_labels(project_id, max_labels, gitlabclient)?;
+    config.members = get_project_members(project_id, max_members, gitlabclient)?;
     config.save(config::GitConfigSaveableLevel::Repo)?;
 
     let out_vars = vec!(("project_id".to_string(), project_id.to_string())).into_iter();
@@ -272,112 +331,27 @@ mod project_attach_unit_tests {
 
     use super::*;
 
-    #[rstest(
-    url, r_type, search_str,
-    case("git@gitlab.com:aiganym_sag/hostel-management-system-master.git", RemoteType::SSH, "hostel-management-system-master"),
-    case("git@gitlab.com:aiganym_sag/hostel/management/system-master.git", RemoteType::SSH, "system-master"),
-    case("ssh://git@gitlab.com:one/two/three.git", RemoteType::SSH, "three"),
-    case("ssh://git@gitlab.com:2222/one/two/three.git", RemoteType::SSH, "three"),
-    case("https://gitlab.com/jandamuda0400/berat-badan-dan-jerawat.git", RemoteType::HTTP, "berat-badan-dan-jerawat"),
-    case("https://gitlab.com/jandamuda0400/berat/badan/dan/jerawat.git", RemoteType::HTTP, "jerawat"),
-    case("http://gitlab.com/jandamuda0400/berat-badan-dan-jerawat.git", RemoteType::HTTP, "berat-badan-dan-jerawat"),
-
-    )]
-    fn test_get_search_param_and_remote_type(url: &str, r_type: RemoteType, search_str: &str) {
-        let (t,s) = get_search_param_and_remote_type(url);
-        assert_eq!(t, r_type);
-        assert_eq!(s, search_str);
+    fn remote_project(remote: &str, project_id: u64, forked_from_project_id: Option<u64>) -> RemoteProject {
+        RemoteProject { remote: remote.to_string(), project_id, forked_from_project_id }
     }
 
     #[rstest(
-    gid_str, pid,
-    case("gid://gitlab/Project/12345", 12345),
+    found, upstream, fork,
+    case(vec!(remote_project("gl", 1, None)), "gl", None),
+    case(vec!(remote_project("origin", 2, Some(1)), remote_project("upstream", 1, None)), "upstream", Some("origin")),
+    case(vec!(remote_project("origin", 1, None), remote_project("mine", 2, Some(1))), "origin", Some("mine")),
+    case(vec!(remote_project("other", 3, None), remote_project("origin", 1, None)), "origin", None),
+    case(vec!(remote_project("origin", 1, None), remote_project("upstream", 3, None)), "upstream", None),
     )]
-    fn test_parse_pid_from_gid(gid_str: &str, pid: u64) {
-        assert_eq!(pid, parse_pid_from_gid(gid_str));
+    fn test_choose_remote_project(found: Vec<RemoteProject>, upstream: &str, fork: Option<&str>) {
+        let (u, f) = choose_remote_project(found).unwrap();
+        assert_eq!(u.remote, upstream);
+        assert_eq!(f.map(|f| f.remote).as_deref(), fork);
     }
 
-    #[rstest(
-    pid, r_type, url, remotes,
-    case(23456, RemoteType::HTTP, "https://gitlab.com:one/two/four.git",
-        projects_with_remotes::ResponseData {
-            projects:
-                Some(
-                    projects_with_remotes::ProjectsWithRemotesProjects {
-                        nodes:
-                            Some(
-                                vec!(
-                                    Some(
-                                        projects_with_remotes::ProjectsWithRemotesProjectsNodes {
-                                            id: "gid://gitlab/Project/12345".to_string(),
-                                            ssh_url_to_repo: Some("ssh://git@gitlab.com:one/two/three.git".to_string()),
-                                            http_url_to_repo: Some("https://gitlab.com:one/two/three.git".to_string()),
-                                        }
-                                    ),
-                                    Some(
-                                        projects_with_remotes::ProjectsWithRemotesProjectsNodes {
-                                            id: "gid://gitlab/Project/23456".to_string(),
-                                            ssh_url_to_repo: Some("ssh://git@gitlab.com:one/two/four.git".to_string()),
-                                            http_url_to_repo: Some("https://gitlab.com:one/two/four.git".to_string()),
-                                        }
-                                    ),
-                                )
-                            )
-                    }
-                )
-        }
-    ),
-    case(12345, RemoteType::SSH, "ssh://git@gitlab.com:one/two/three.git",
-        projects_with_remotes::ResponseData {
-            projects:
-                Some(
-                    projects_with_remotes::ProjectsWithRemotesProjects {
-                        nodes:
-                            Some(
-                                vec!(
-                                    Some(
-                                        projects_with_remotes::ProjectsWithRemotesProjectsNodes {
-                                            id: "gid://gitlab/Project/12345".to_string(),
-                                            ssh_url_to_repo: Some("ssh://git@gitlab.com:one/two/three.git".to_string()),
-                                            http_url_to_repo: Some("https://gitlab.com:one/two/three.git".to_string()),
-                                        }
-                                    ),
-                                    Some(
-                                        projects_with_remotes::ProjectsWithRemotesProjectsNodes {
-                                            id: "gid://gitlab/Project/23456".to_string(),
-                                            ssh_url_to_repo: Some("ssh://git@gitlab.com:one/two/four.git".to_string()),
-                                            http_url_to_repo: Some("https://gitlab.com:one/two/four.git".to_string()),
-                                        }
-                                    ),
-                                )
-                            )
-                    }
-                )
-        }
-    ),
-    )]
-    fn test_find_project_id_good(pid: u64, r_type: RemoteType, url: &str, remotes: projects_with_remotes::ResponseData) {
-        assert_eq!(pid, find_project_id(r_type, url, remotes).unwrap());
-    }
-    #[rstest(
-    r_type, url, remotes,
-    case(RemoteType::SSH, "ssh://git@gitlab.com:one/two/three.git",
-        projects_with_remotes::ResponseData {
-            projects:
-                Some(
-                    projects_with_remotes::ProjectsWithRemotesProjects {
-                        nodes:
-                            Some(
-                                vec!()
-                            )
-                    }
-                )
-        }
-    ),
-    )]
-    fn test_find_project_id_bad(r_type: RemoteType, url: &str, remotes: projects_with_remotes::ResponseData) {
-        assert!(
-            find_project_id(r_type, url, remotes).is_err(),
-        );
+    #[test]
+    fn test_choose_remote_project_ambiguous() {
+        let found = vec!(remote_project("one", 1, None), remote_project("two", 2, None));
+        assert!(choose_remote_project(found).is_err());
     }
 }
@@ -0,0 +1,89 @@
+//! This module implements cloning a GitLab project into a new local repo, and then attaching the
+//! project to it in one step.
+use std::env;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::{anyhow, Context, Result};
+use clap::value_t;
+use serde::Deserialize;
+
+use crate::cmds::project::attach::attach_project;
+use crate::config;
+use crate::gitlab::Project as GLProject;
+use crate::gitlab::{Client, Query};
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    id: u64,
+    path: String,
+    ssh_url_to_repo: String,
+    http_url_to_repo: String,
+}
+
+fn git_clone(url: &str, directory: &str) -> Result<()> {
+    let status = Command::new("git")
+        .args(&["clone", url, directory])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()?
+        .wait()?;
+
+    if !status.success() {
+        return Err(anyhow!("Failed to clone {}", url));
+    }
+    Ok(())
+}
+
+pub fn clone_project_cmd(args: clap::ArgMatches, _config: config::Config, gitlabclient: Client) -> Result<()> {
+    let project_arg = args.value_of("project").unwrap();
+
+    // a numeric argument is a project ID, otherwise it's taken to be a `namespace/path`
+    let mut p = GLProject::builder();
+    match project_arg.parse::<u64>() {
+        Ok(id) => p.project(id),
+        Err(_) => p.project(project_arg),
+    };
+    let endpoint = p.build()
+        .map_err(|e| anyhow!("Could not construct query to fetch project from server.\n {}",e))?;
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    let project: Project = endpoint
+        .query(&gitlabclient)
+        .with_context(|| format!("Failed to find project {}", project_arg))?;
+
+    debug!("project: {:#?}", project);
+
+    let url = if args.is_present("http") {
+        &project.http_url_to_repo
+    } else {
+        &project.ssh_url_to_repo
+    };
+
+    let directory = args.value_of("directory").unwrap_or(&project.path);
+
+    if Path::new(directory).exists() {
+        return Err(anyhow!("Destination path {} already exists", directory));
+    }
+
+    git_clone(url, directory)?;
+
+    // re-read config from inside the new repo so that the project is attached to it, and not to
+    // any repo we may have been invoked from
+    env::set_current_dir(directory)
+        .with_context(|| format!("Could not change directory to {}", directory))?;
+    let mut repo_config = config::Config::defaults();
+    repo_config.remote = Some("origin".to_string());
+
+    trace!("Repo config: {:?}", repo_config);
+
+    attach_project(
+        project.id,
+        value_t!(args, "max_labels", u64).unwrap(),
+        value_t!(args, "max_members", u64).unwrap(),
+        repo_config,
+        &gitlabclient
+    )
+}
@@ -0,0 +1,152 @@
+//! This module implements forking the attached GitLab project, adding the fork as a git remote of
+//! the local repo and recording it in config so that merge requests can be raised from it.
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use git2::Repository;
+use serde::Deserialize;
+
+use crate::config;
+use crate::gitlab::Project as GLProject;
+use crate::gitlab::{Client, ForkProject, Query};
+use crate::utils;
+
+/// How long to wait for the server to finish creating the fork before giving up
+const FORK_TIMEOUT: Duration = Duration::from_secs(300);
+const FORK_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    id: u64,
+    path_with_namespace: String,
+    ssh_url_to_repo: String,
+    http_url_to_repo: String,
+    import_status: Option<String>,
+}
+
+/// Forks are created asynchronously by the server, so poll the new project until its repository
+/// has been populated
+fn wait_for_fork(project_id: u64, gitlabclient: &Client) -> Result<Project> {
+    let start = Instant::now();
+
+    loop {
+        let endpoint = GLProject::builder()
+            .project(project_id)
+            .build()
+            .map_err(|e| anyhow!("Could not construct query to fetch project from server.\n {}",e))?;
+
+        let project: Project = endpoint
+            .query(gitlabclient)
+            .context("Failed to query status of forked project")?;
+
+        debug!("fork import status: {:#?}", project.import_status);
+
+        match project.import_status.as_deref() {
+            None | Some("none") | Some("finished") => return Ok(project),
+            Some("failed") => return Err(anyhow!("GitLab failed to create fork {}", project.path_with_namespace)),
+            _ if start.elapsed() > FORK_TIMEOUT => {
+                return Err(anyhow!("Timed out waiting for fork {} to be created", project.path_with_namespace))
+            }
+            _ => thread::sleep(FORK_POLL_INTERVAL),
+        }
+    }
+}
+
+/// Add the remote to the local repo, or point it at the fork if it already exists
+fn add_or_update_remote(repo: &Repository, name: &str, url: &str) -> Result<()> {
+    match repo.find_remote(name) {
+        Ok(_) => repo.remote_set_url(name, url)
+            .with_context(|| format!("Failed to set URL of git remote {}", name)),
+        Err(_) => repo.remote(name, url)
+            .map(|_| ())
+            .with_context(|| format!("Failed to add git remote {}", name)),
+    }
+}
+
+fn git_push_set_upstream(remote: &str, branch: &str) -> Result<()> {
+    let status = Command::new("git")
+        .args(&["push", "--set-upstream", remote, branch])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()?
+        .wait()?;
+
+    if !status.success() {
+        return Err(anyhow!("Failed to push {} to {}", branch, remote));
+    }
+    Ok(())
+}
+
+pub fn fork_project_cmd(args: clap::ArgMatches, mut config: config::Config, gitlabclient: Client) -> Result<()> {
+    // if not inside local repo error and exit
+    let repo_path = config.repo_path.clone()
+        .ok_or_else(|| anyhow!("Local repo not found. Are you in the correct directory?"))?;
+
+    let project_id = utils::get_proj_from_arg_or_conf(&args, &config)?;
+
+    let mut f = ForkProject::builder();
+    f.project(project_id);
+
+    // a numeric namespace is a namespace ID, otherwise it's taken to be a namespace path
+    if let Some(n) = args.value_of("namespace") {
+        match n.parse::<u64>() {
+            Ok(id) => f.namespace_id(id),
+            Err(_) => f.namespace_path(n),
+        };
+    }
+
+    if let Some(p) = args.value_of("path") {
+        f.path(p);
+    }
+
+    if let Some(n) = args.value_of("name") {
+        f.name(n);
+    }
+
+    let endpoint = f.build()
+        .map_err(|e| anyhow!("Could not construct API call to fork project.\n {}",e))?;
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    let fork: Project = endpoint
+        .query(&gitlabclient)
+        .context("Failed to fork project")?;
+
+    println!("Forking project into {}...", fork.path_with_namespace);
+
+    let fork = wait_for_fork(fork.id, &gitlabclient)?;
+
+    let remote = args.value_of("remote").unwrap();
+    let url = if args.is_present("http") {
+        &fork.http_url_to_repo
+    } else {
+        &fork.ssh_url_to_repo
+    };
+
+    let repo = Repository::open(&repo_path)
+        .context("Could not find local repo")?;
+    add_or_update_remote(&repo, remote, url)?;
+
+    println!("Added git remote {} for {}", remote, url);
+
+    if !args.is_present("no_push") {
+        let branch = utils::get_current_local_branch_name(&repo_path)?;
+        git_push_set_upstream(remote, &branch)?;
+    }
+
+    config.fork_project_id = Some(fork.id);
+    config.fork_remote = Some(remote.to_string());
+    config.save(config::GitConfigSaveableLevel::Repo)?;
+
+    utils::write_short_output(
+        config.format,
+        vec![
+            ("fork_project_id".to_string(), fork.id.to_string()),
+            ("fork_path".to_string(), fork.path_with_namespace),
+            ("remote".to_string(), remote.to_string()),
+        ].into_iter()
+    )
+}
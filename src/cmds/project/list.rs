@@ -0,0 +1,200 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Local};
+use chrono_humanize::HumanTime;
+use clap::{value_t_or_exit, values_t_or_exit};
+use comfy_table::*;
+
+use crate::cmds::project::Project;
+use crate::config;
+use crate::config::OutputFormat;
+use crate::gitlab::api::{Endpoint, Pageable};
+use crate::gitlab::converter::{
+    group_projects_order_by_from_str, project_order_by_from_str, visibility_level_from_str,
+};
+use crate::gitlab::{
+    api, Client, GroupProjects, GroupProjectsBuilder, Projects, ProjectsBuilder, Query, SortOrder,
+};
+
+pub fn generate_projects_builder<'a>(
+    args: &'a clap::ArgMatches,
+    p: &'a mut ProjectsBuilder<'a>,
+) -> Result<Projects<'a>> {
+
+    for arg in &args.args {
+        let (key, _) = arg;
+        match *key {
+            "member" => p.membership(true),
+            "owned" => p.owned(true),
+            "starred" => p.starred(true),
+            "visibility" => p.visibility(visibility_level_from_str(args.value_of("visibility").unwrap()).unwrap()),
+            "filter" => p.search(args.value_of("filter").unwrap()),
+            "archived" => p.archived(true),
+            "order_by" => {
+                p.order_by(project_order_by_from_str(args.value_of("order_by").unwrap()).unwrap())
+            }
+            "descending" => p.sort(SortOrder::Descending),
+            "ascending" => p.sort(SortOrder::Ascending),
+            // presentational arguments
+            "max" => p,
+            "fields" => p,
+            "no_headers" => p,
+            "human_friendly" => p,
+            _ => unreachable!(),
+        };
+    }
+    p.build()
+        .map_err(|e| anyhow!("Could not construct projects query.\n {}", e))
+}
+
+pub fn generate_group_projects_builder<'a>(
+    args: &'a clap::ArgMatches,
+    p: &'a mut GroupProjectsBuilder<'a>,
+) -> Result<GroupProjects<'a>> {
+
+    for arg in &args.args {
+        let (key, _) = arg;
+        match *key {
+            "group" => p.group(args.value_of("group").unwrap()),
+            "include_subgroups" => p.include_subgroups(true),
+            "owned" => p.owned(true),
+            "starred" => p.starred(true),
+            "visibility" => p.visibility(visibility_level_from_str(args.value_of("visibility").unwrap()).unwrap()),
+            "filter" => p.search(args.value_of("filter").unwrap()),
+            "archived" => p.archived(true),
+            "order_by" => {
+                p.order_by(group_projects_order_by_from_str(args.value_of("order_by").unwrap()).unwrap())
+            }
+            "descending" => p.sort(SortOrder::Descending),
+            "ascending" => p.sort(SortOrder::Ascending),
+            // presentational arguments
+            "max" => p,
+            "fields" => p,
+            "no_headers" => p,
+            "human_friendly" => p,
+            _ => unreachable!(),
+        };
+    }
+    p.build()
+        .map_err(|e| anyhow!("Could not construct group projects query.\n {}", e))
+}
+
+fn print_projects(projects: Vec<Project>, fields: Vec<String>, no_headers: bool, human: bool) {
+    let mut table = Table::new();
+
+    table
+        .load_preset("                   ")
+        .set_content_arrangement(ContentArrangement::Dynamic);
+
+    if !no_headers {
+        table.add_row(fields.iter().map(|f| Cell::new(f.to_uppercase().replace("_"," ")).set_alignment(CellAlignment::Center)));
+    }
+
+    for p in projects {
+        let mut r: Vec<Cell> =Vec::new();
+
+        for field in &fields {
+            match field.as_str() {
+                "created_on" =>
+                        if human {
+                            r.push(Cell::new(HumanTime::from(p.created_at)))
+                        } else {
+                            let d: DateTime<Local> = DateTime::from(p.created_at);
+                            r.push(Cell::new(d.format("%Y-%m-%d %H:%M:%S").to_string()))
+                        }
+                "default_branch" => {
+                    match &p.default_branch {
+                        Some(b) => r.push(Cell::new(b)),
+                        None => r.push(Cell::new("-").set_alignment(CellAlignment::Center)),
+                    }
+                },
+                "description" => {
+                    match &p.description {
+                        Some(d) if !d.is_empty() => r.push(Cell::new(d)),
+                        _ => r.push(Cell::new("-").set_alignment(CellAlignment::Center)),
+                    }
+                },
+                "forks" => r.push(Cell::new(p.forks_count).set_alignment(CellAlignment::Right)),
+                "http_url" => r.push(Cell::new(p.http_url_to_repo.clone())),
+                "id" => r.push(Cell::new(p.id).set_alignment(CellAlignment::Right)),
+                "last_activity_on" =>
+                        if human {
+                            r.push(Cell::new(HumanTime::from(p.last_activity_at)))
+                        } else {
+                            let d: DateTime<Local> = DateTime::from(p.last_activity_at);
+                            r.push(Cell::new(d.format("%Y-%m-%d %H:%M:%S").to_string()))
+                        }
+                "name" => r.push(Cell::new(p.name.clone())),
+                "path" => r.push(Cell::new(p.path_with_namespace.clone())),
+                "ssh_url" => r.push(Cell::new(p.ssh_url_to_repo.clone())),
+                "stars" => r.push(Cell::new(p.star_count).set_alignment(CellAlignment::Right)),
+                "visibility" => r.push(Cell::new(p.visibility.clone())),
+                "web_url" => r.push(Cell::new(p.web_url.clone())),
+                _ => unreachable!(""),
+            }
+        }
+
+        table.add_row(r);
+    }
+    println!("{}", table);
+}
+
+/// Run the projects query (which may be the instance-wide or group-scoped endpoint) and print the
+/// results in the configured format.
+fn query_and_print_projects<E>(
+    endpoint: E,
+    args: &clap::ArgMatches,
+    config: &config::Config,
+    gitlabclient: &Client,
+) -> Result<()>
+where
+    E: Endpoint + Pageable,
+{
+    let max = value_t_or_exit!(args, "max", u32);
+
+    match config.format {
+        Some(OutputFormat::JSON) => {
+            let raw_json = api::raw(endpoint)
+                .query(gitlabclient)
+                .context("Failed to query projects")?;
+
+            println!("{}", String::from_utf8(raw_json).unwrap());
+            Ok(())
+        }
+
+        Some(OutputFormat::Text) => {
+            let projects: Vec<Project> = api::paged(endpoint, api::Pagination::Limit(max as usize))
+                .query(gitlabclient)
+                .context("Failed to query projects")?;
+
+            print_projects(
+                projects,
+                values_t_or_exit!(args, "fields", String),
+                args.occurrences_of("no_headers")>0,
+                args.occurrences_of("human_friendly")>0
+                );
+            Ok(())
+        }
+        _ => Err(anyhow!("Bad output format in config")),
+    }
+}
+
+pub fn list_projects_cmd(
+    args: clap::ArgMatches,
+    config: config::Config,
+    gitlabclient: Client,
+) -> Result<()> {
+
+    debug!("args: {:#?}", args);
+
+    if args.is_present("group") {
+        let mut p = GroupProjects::builder();
+        let endpoint = generate_group_projects_builder(&args, &mut p)?;
+        debug!("endpoint: {:#?}", endpoint);
+        query_and_print_projects(endpoint, &args, &config, &gitlabclient)
+    } else {
+        let mut p = Projects::builder();
+        let endpoint = generate_projects_builder(&args, &mut p)?;
+        debug!("endpoint: {:#?}", endpoint);
+        query_and_print_projects(endpoint, &args, &config, &gitlabclient)
+    }
+}
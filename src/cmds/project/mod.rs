@@ -1,5 +1,9 @@
+mod approval_rules;
 mod attach;
+mod clone;
 mod create;
+mod fork;
+mod list;
 mod open;
 mod show;
 
@@ -19,9 +23,14 @@ use crate::utils;
 #[derive(Debug, Deserialize)]
 pub struct Project {
     id: u64,
+    name: String,
+    path_with_namespace: String,
+    description: Option<String>,
+    default_branch: Option<String>,
     owner: Option<Map<String, Value>>,
     web_url: String,
     created_at: DateTime<Utc>,
+    last_activity_at: DateTime<Utc>,
     ssh_url_to_repo: String,
     http_url_to_repo: String,
     forks_count: u64,
@@ -54,6 +63,213 @@ impl subcommand::SubCommand for ProjectCmd<'_> {
             .setting(clap::AppSettings::ColoredHelp)
             .setting(clap::AppSettings::VersionlessSubcommands)
             .setting(clap::AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                clap::SubCommand::with_name("approval-rules")
+                    .about("Manages merge request approval rules")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .setting(clap::AppSettings::VersionlessSubcommands)
+                    .setting(clap::AppSettings::SubcommandRequiredElseHelp)
+                    .subcommand(
+                        clap::SubCommand::with_name("list")
+                            .about("Lists the project's approval rules")
+                            .setting(clap::AppSettings::ColoredHelp)
+                            .arg(utils::project_id_arg("Project ID to look for approval rules in. Defaults to attached Project ID."))
+                    )
+                    .subcommand(
+                        clap::SubCommand::with_name("create")
+                            .about("Creates an approval rule")
+                            .setting(clap::AppSettings::ColoredHelp)
+                            .arg(
+                                clap::Arg::with_name("name")
+                                    .help("Name of the approval rule")
+                                    .takes_value(true)
+                                    .empty_values(false)
+                                    .required(true)
+                            )
+                            .arg(
+                                clap::Arg::with_name("approvals")
+                                    .long("approvals")
+                                    .short("a")
+                                    .help("Number of approvals required")
+                                    .takes_value(true)
+                                    .empty_values(false)
+                                    .required(true)
+                                    .validator(validator::check_u64)
+                            )
+                            .arg(
+                                clap::Arg::with_name("users")
+                                    .long("users")
+                                    .short("u")
+                                    .help("Username(s) of the users who can approve")
+                                    .takes_value(true)
+                                    .multiple(true)
+                                    .empty_values(false)
+                                    .require_delimiter(true)
+                            )
+                            .arg(
+                                clap::Arg::with_name("groups")
+                                    .long("groups")
+                                    .short("g")
+                                    .help("ID(s) of the groups whose members can approve")
+                                    .takes_value(true)
+                                    .multiple(true)
+                                    .empty_values(false)
+                                    .require_delimiter(true)
+                                    .validator(validator::check_u64)
+                            )
+                            .arg(
+                                clap::Arg::with_name("branches")
+                                    .long("branches")
+                                    .short("b")
+                                    .help("Protected branch(es) the rule applies to. Defaults to all branches.")
+                                    .takes_value(true)
+                                    .multiple(true)
+                                    .empty_values(false)
+                                    .require_delimiter(true)
+                            )
+                            .arg(utils::project_id_arg("Project ID to look for approval rules in. Defaults to attached Project ID."))
+                            .after_help(
+"Usernames are looked up amongst the project members cached by `git lab project attach`.",
+                            ),
+                    )
+            )
+            .subcommand(
+                clap::SubCommand::with_name("list")
+                    .about("Lists projects")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("member")
+                            .long("member")
+                            .short("m")
+                            .help("Only return projects that you are a member of")
+                            .conflicts_with("group")
+                    )
+                    .arg(
+                        clap::Arg::with_name("owned")
+                            .long("owned")
+                            .short("o")
+                            .help("Only return projects that you own")
+                    )
+                    .arg(
+                        clap::Arg::with_name("starred")
+                            .long("starred")
+                            .short("s")
+                            .help("Only return projects that you have starred")
+                    )
+                    .arg(
+                        clap::Arg::with_name("archived")
+                            .long("archived")
+                            .help("Only return archived projects")
+                    )
+                    .arg(
+                        clap::Arg::with_name("visibility")
+                            .long("visibility")
+                            .short("v")
+                            .help("Filter projects by visibility")
+                            .takes_value(true)
+                            .possible_values(&["public", "internal", "private"])
+                    )
+                    .arg(
+                        clap::Arg::with_name("group")
+                            .long("group")
+                            .short("g")
+                            .help("Only return projects in a group (ID or full path)")
+                            .takes_value(true)
+                            .empty_values(false)
+                    )
+                    .arg(
+                        clap::Arg::with_name("include_subgroups")
+                            .long("include_subgroups")
+                            .help("Include projects in the group's subgroups")
+                            .requires("group")
+                    )
+                    .arg(
+                        clap::Arg::with_name("filter")
+                            .long("filter")
+                            .short("f")
+                            .help("Filter projects by search string")
+                            .takes_value(true)
+                            .empty_values(false)
+                    )
+                    .arg(
+                        clap::Arg::with_name("fields")
+                            .long("fields")
+                            .short("F")
+                            .help("Specify which fields to output")
+                            .takes_value(true)
+                            .multiple(true)
+                            .require_delimiter(true)
+                            .possible_values(
+                                &[
+                                "created_on", // created_at
+                                "default_branch",
+                                "description",
+                                "forks", // forks_count
+                                "http_url", // http_url_to_repo
+                                "id",
+                                "last_activity_on", // last_activity_at
+                                "name",
+                                "path", // path_with_namespace
+                                "ssh_url", // ssh_url_to_repo
+                                "stars", // star_count
+                                "visibility",
+                                "web_url",
+                                ])
+                            .default_value("id,path,description")
+                    )
+                    .arg(
+                        clap::Arg::with_name("no_headers")
+                            .long("no_headers")
+                            .help("Suppress header row on text output")
+                    )
+                    .arg(
+                        clap::Arg::with_name("human_friendly")
+                            .short("h")
+                            .help("Use human-friendly date-time strings")
+                    )
+                    .arg(
+                        clap::Arg::with_name("order_by")
+                            .long("order_by")
+                            .help("Order results by given field")
+                            .takes_value(true)
+                            .possible_values(
+                                &[
+                                "created_on",
+                                "id",
+                                "last_activity_on",
+                                "name",
+                                "path",
+                                "updated_on",
+                                ])
+                            .default_value("created_on")
+                    )
+                    .arg(
+                        clap::Arg::with_name("descending")
+                            .long("desc")
+                            .short("D")
+                            .help("Sort results in descending order")
+                    )
+                    .arg(
+                        clap::Arg::with_name("ascending")
+                            .long("asc")
+                            .short("A")
+                            .help("Sort results in ascending order")
+                    )
+                    .arg(
+                        clap::Arg::with_name("max")
+                            .long("max")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .default_value("40")
+                            .help("Maximum records to return")
+                            .validator(validator::check_u32)
+                    )
+                    .after_help(
+"Without any filters this will list every project visible to you on the server, which on a large \
+instance like gitlab.com is a great many. Use `--member`, `--owned`, `--group` or `--filter` to \
+narrow the search. The `id` column can be passed to `git lab project attach --project_id`.",
+                    ),
+            )
             .subcommand(
                 clap::SubCommand::with_name("show")
                     .about("Shows project information in the terminal")
@@ -104,11 +320,19 @@ to use. If this is not set, on Linux, it will try `xdg-open(1)`",
                         clap::Arg::with_name("project_id")
                             .short("p")
                             .long("project_id")
-                            .help("Project ID to attach")
+                            .help("Project ID to attach (see `git lab project list`)")
                             .empty_values(false)
                             .takes_value(true)
                             .validator(validator::check_u64)
                     )
+                    .arg(
+                        clap::Arg::with_name("remote")
+                            .short("r")
+                            .long("remote")
+                            .help("Git remote pointing at the project. Defaults to `gitlab.remote` or is detected")
+                            .empty_values(false)
+                            .takes_value(true)
+                    )
                     .arg(
                         clap::Arg::with_name("max_members")
                             .short("m")
@@ -137,13 +361,132 @@ attached project's identifier and cached metadata when they are invoked.\
 \n
 If the project ID is passed it will be attached without verification against the GitLab server and \
 used to hydrate the cache. If not, the command will try to infer which project to attach to by \
-matching a locally configured git remote with any project with the same remote configured on the \
-server. If a match is found it will be attached and used to populate or refresh the local metadata \
+looking up the project at the path given in a locally configured git remote's URL (SSH, scp-like or \
+HTTP(S), with or without a `.git` suffix) on the server. If a match is found it will be attached and used to populate or refresh the local metadata \
 cache.\
 \n
+The remote used is the one passed with `--remote`, or configured with `gitlab.remote`. Otherwise \
+every remote whose URL points at the configured GitLab host is looked up, falling back on `origin`. \
+Where these turn out to be a project and a fork of it, the upstream project is attached and the \
+fork is recorded as if set up by `git lab project fork`. The remote chosen is saved as \
+`gitlab.remote` for use by other commands.\
+\n
 Specific project metadata that is cached includes project member usernames and labels. If invoked \
 outside the context of a local repo, the command will fail.",),
             )
+            .subcommand(
+                clap::SubCommand::with_name("clone")
+                    .about("Clones a GitLab project and attaches it to the new local repo")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("project")
+                            .help("Project to clone, as `namespace/path` or project ID")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .required(true)
+                    )
+                    .arg(
+                        clap::Arg::with_name("directory")
+                            .help("Directory to clone into. Defaults to the project's path")
+                            .takes_value(true)
+                            .empty_values(false)
+                    )
+                    .arg(
+                        clap::Arg::with_name("http")
+                            .long("http")
+                            .help("Clone over HTTP(S) rather than SSH")
+                    )
+                    .arg(
+                        clap::Arg::with_name("max_members")
+                            .short("m")
+                            .long("max_members")
+                            .help("Maximum number of project member details to cache locally")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .default_value("80")
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("max_labels")
+                            .short("l")
+                            .long("max_labels")
+                            .help("Maximum number of labels to cache locally")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .default_value("80")
+                            .validator(validator::check_u64)
+                    )
+                    .after_help(
+"The project is looked up on the GitLab server and cloned using git-clone(1) over SSH, or HTTP(S) if \
+`--http` is passed. Once cloned, the new repo is attached to the project as if `git lab project \
+attach` had been run inside it, so the project's metadata cache is populated immediately.",
+                    ),
+            )
+            .subcommand(
+                clap::SubCommand::with_name("fork")
+                    .about("Forks the attached project and adds the fork as a git remote")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .setting(clap::AppSettings::DeriveDisplayOrder)
+                    .arg(
+                        clap::Arg::with_name("namespace")
+                            .short("n")
+                            .long("namespace")
+                            .help("Namespace ID or path to fork into. Defaults to your user namespace")
+                            .takes_value(true)
+                            .empty_values(false)
+                    )
+                    .arg(
+                        clap::Arg::with_name("path")
+                            .long("path")
+                            .help("Path to give the fork")
+                            .takes_value(true)
+                            .empty_values(false)
+                    )
+                    .arg(
+                        clap::Arg::with_name("name")
+                            .long("name")
+                            .help("Name to give the fork")
+                            .takes_value(true)
+                            .empty_values(false)
+                    )
+                    .arg(
+                        clap::Arg::with_name("remote")
+                            .short("r")
+                            .long("remote")
+                            .help("Name of the git remote to add for the fork")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .default_value("fork")
+                    )
+                    .arg(
+                        clap::Arg::with_name("http")
+                            .long("http")
+                            .help("Add the remote using the fork's HTTP(S) URL rather than SSH")
+                    )
+                    .arg(
+                        clap::Arg::with_name("no_push")
+                            .long("no_push")
+                            .help("Do not push the current branch to the fork")
+                    )
+                    .arg(
+                        clap::Arg::with_name("project_id")
+                            .short("p")
+                            .long("project_id")
+                            .help("Project ID to fork. Defaults to the attached project")
+                            .empty_values(false)
+                            .takes_value(true)
+                            .validator(validator::check_u64)
+                    )
+                    .after_help(
+"The project is forked on the GitLab server, and once the server has finished creating the fork it \
+is added to the local repo as a git remote (named `fork` unless `--remote` is passed), and the \
+current branch is pushed to it and set to track it.\
+\n
+The fork's project ID and remote are saved in the local repo's git config, alongside the attached \
+project, so that `git lab mr create` raises merge requests from branches in the fork against the \
+attached (upstream) project.",
+                    ),
+            )
             .subcommand(
                 clap::SubCommand::with_name("create")
                     .about("Creates a GitLab project")
@@ -452,8 +795,12 @@ If you have errors using the `*_disabled` flags your GitLab server may no longer
         let gitlabclient = gitlab::new(&config).context("Could not create GitLab client connection.")?;
 
         match args.subcommand() {
+            ("approval-rules", Some(a)) => approval_rules::approval_rules_cmd(a.clone(), config, *gitlabclient)?,
+            ("clone", Some(a)) => clone::clone_project_cmd(a.clone(), config, *gitlabclient)?,
             ("create", Some(a)) => create::create_project_cmd(a.clone(), config, *gitlabclient)?,
+            ("fork", Some(a)) => fork::fork_project_cmd(a.clone(), config, *gitlabclient)?,
             ("attach", Some(a)) => attach::attach_project_cmd(a.clone(), config, *gitlabclient)?,
+            ("list", Some(a)) => list::list_projects_cmd(a.clone(), config, *gitlabclient)?,
             ("open", Some(a)) => open::open_project_cmd(a.clone(), config, *gitlabclient)?,
             ("show", Some(a)) => show::show_project_cmd(a.clone(), config, *gitlabclient)?,
             _ => unreachable!(),
@@ -0,0 +1,396 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Local, Utc};
+use chrono_humanize::HumanTime;
+use clap::value_t_or_exit;
+use colored::*;
+use comfy_table::*;
+use serde_json::{json, Map, Value};
+
+use crate::cmds::{issue, mr, todo};
+use crate::config;
+use crate::config::OutputFormat;
+use crate::gitlab;
+use crate::gitlab::MergeRequest as GLMergeRequest;
+use crate::gitlab::{
+    api, Client, CurrentUser, IssueScope, IssueState, Issues, MergeRequestApprovalState,
+    MergeRequestApprovals, MergeRequestScope, MergeRequestState, MergeRequests, MergeRequestsWithReviewer, Query,
+    TodoState, Todos,
+};
+use crate::subcommand;
+use crate::utils;
+use crate::utils::validator;
+
+/// This implements the `status` command, a dashboard of the merge requests, issues and to-do items
+/// in the attached project that need the user's attention.
+pub struct StatusCmd<'a> {
+    pub clap_cmd: clap::App<'a, 'a>,
+}
+
+impl subcommand::SubCommand for StatusCmd<'_> {
+    fn gen_clap_command(&self) -> clap::App {
+        let c = self.clap_cmd.clone();
+        c.about("Shows merge requests, issues and to-do items that need your attention")
+            .visible_alias("dashboard")
+            .setting(clap::AppSettings::ColoredHelp)
+            .arg(
+                clap::Arg::with_name("max")
+                    .long("max")
+                    .takes_value(true)
+                    .empty_values(false)
+                    .default_value("10")
+                    .help("Maximum records to return in each section")
+                    .validator(validator::check_u32)
+            )
+            .arg(
+                clap::Arg::with_name("human_friendly")
+                    .short("h")
+                    .help("Use human-friendly date-time strings")
+            )
+            .after_help(
+"This command shows, for the attached project, the open merge requests which you have been asked \
+to review and have not yet approved, the open merge requests you created along with their pipeline \
+and approval state, the open issues assigned to you and your pending to-do items.
+
+Merge requests you can approve under an approval rule which isn't yet satisfied are listed for \
+review too, where the project has approval rules, which need GitLab Premium. Only the latest open \
+merge requests, up to `--max`, are checked for these.",
+            )
+    }
+
+    fn run(&self, config: config::Config, args: clap::ArgMatches) -> Result<()> {
+        let gitlabclient = gitlab::new(&config).context("Could not create GitLab client connection.")?;
+
+        status_cmd(args, config, *gitlabclient)
+    }
+}
+
+/// Whether a list of users, as returned by the API, includes the user
+fn includes_user(users: &Value, user_id: u64) -> bool {
+    users
+        .as_array()
+        .map(|a| a.iter().any(|u| u["id"].as_u64() == Some(user_id) || u["user"]["id"].as_u64() == Some(user_id)))
+        .unwrap_or(false)
+}
+
+/// Whether the user is an eligible approver under one of a merge request's approval rules which
+/// isn't yet satisfied, and hasn't already approved under it
+fn can_approve(approval_state: &Value, user_id: u64) -> bool {
+    approval_state["rules"]
+        .as_array()
+        .map(|rules| rules.iter().any(|r| {
+            r["approved"].as_bool() == Some(false)
+                && includes_user(&r["eligible_approvers"], user_id)
+                && !includes_user(&r["approved_by"], user_id)
+        }))
+        .unwrap_or(false)
+}
+
+/// Open merge requests awaiting the user's review or approval: those they are a reviewer of but
+/// haven't approved, and those they can approve under an approval rule which isn't yet satisfied
+fn review_requests(
+    args: &clap::ArgMatches,
+    config: &config::Config,
+    gitlabclient: &Client,
+    user_id: u64,
+) -> Result<Vec<Value>> {
+    let project_id = utils::get_proj_from_arg_or_conf(args, config)?;
+    let max = value_t_or_exit!(args, "max", u32) as usize;
+
+    let mut m = MergeRequests::builder();
+    m.scope(MergeRequestScope::All).state(MergeRequestState::Opened);
+    let endpoint = MergeRequestsWithReviewer::new(
+        mr::list::generate_mrs_builder(args, config, &mut m)?,
+        Some(user_id),
+    );
+
+    let mrs: Vec<Value> = api::paged(endpoint, api::Pagination::Limit(max))
+        .query(gitlabclient)
+        .context("Failed to query merge requests to review")?;
+
+    let mut awaiting = Vec::new();
+    for mr in mrs {
+        // list the merge request anyway if its approvals can't be found
+        let approved = match mr_approvals(project_id, mr["iid"].as_u64().unwrap(), gitlabclient) {
+            Ok(approvals) => includes_user(&approvals["approved_by"], user_id),
+            Err(e) => {
+                debug!("{:#}", e);
+                false
+            },
+        };
+
+        if !approved {
+            awaiting.push(mr);
+        }
+    }
+
+    let mut m = MergeRequests::builder();
+    m.scope(MergeRequestScope::All).state(MergeRequestState::Opened);
+    let endpoint = mr::list::generate_mrs_builder(args, config, &mut m)?;
+
+    let mrs: Vec<Value> = api::paged(endpoint, api::Pagination::Limit(max))
+        .query(gitlabclient)
+        .context("Failed to query merge requests to approve")?;
+
+    for mr in mrs {
+        if awaiting.len() >= max {
+            break
+        }
+        if mr["author"]["id"].as_u64() == Some(user_id) || awaiting.iter().any(|a| a["iid"] == mr["iid"]) {
+            continue
+        }
+
+        let state = MergeRequestApprovalState::builder()
+            .project(project_id)
+            .merge_request(mr["iid"].as_u64().unwrap())
+            .build()
+            .map_err(|e| anyhow!("Could not construct merge request approval state query.\n {}", e))?
+            .query(gitlabclient);
+
+        match state {
+            Ok(s) if can_approve(&s, user_id) => awaiting.push(mr),
+            Ok(_) => (),
+            // approval rules need GitLab Premium, so stop looking once they turn out not to be there
+            Err(e) => {
+                debug!("Not looking for merge requests to approve: {}", e);
+                break
+            },
+        }
+    }
+
+    Ok(awaiting)
+}
+
+/// Open merge requests created by the user, with their head pipeline and approval state where
+/// these can be found
+fn authored_mrs(args: &clap::ArgMatches, config: &config::Config, gitlabclient: &Client) -> Result<Vec<Value>> {
+    let project_id = utils::get_proj_from_arg_or_conf(args, config)?;
+    let max = value_t_or_exit!(args, "max", u32);
+
+    let mut m = MergeRequests::builder();
+    m.scope(MergeRequestScope::CreatedByMe).state(MergeRequestState::Opened);
+    let endpoint = mr::list::generate_mrs_builder(args, config, &mut m)?;
+
+    let mrs: Vec<Map<String, Value>> = api::paged(endpoint, api::Pagination::Limit(max as usize))
+        .query(gitlabclient)
+        .context("Failed to query merge requests")?;
+
+    // the head pipeline is only returned when querying a single merge request
+    let mut authored = Vec::new();
+    for listed in mrs {
+        let iid = listed["iid"].as_u64().unwrap();
+
+        let endpoint = GLMergeRequest::builder()
+            .project(project_id)
+            .merge_request(iid)
+            .build()
+            .map_err(|e| anyhow!("Could not construct merge request query.\n {}", e))?;
+
+        let mut mr: Map<String, Value> = endpoint
+            .query(gitlabclient)
+            .unwrap_or_else(|e| {
+                debug!("Failed to find merge request !{}: {}", iid, e);
+                listed
+            });
+
+        match mr_approvals(project_id, iid, gitlabclient) {
+            Ok(approvals) => {
+                mr.insert("approvals".to_string(), approvals);
+            },
+            Err(e) => debug!("{:#}", e),
+        }
+        authored.push(Value::Object(mr));
+    }
+
+    Ok(authored)
+}
+
+fn mr_approvals(project_id: u64, mr_id: u64, gitlabclient: &Client) -> Result<Value> {
+    MergeRequestApprovals::builder()
+        .project(project_id)
+        .merge_request(mr_id)
+        .build()
+        .map_err(|e| anyhow!("Could not construct merge request approvals query.\n {}", e))?
+        .query(gitlabclient)
+        .with_context(|| format!("Failed to query approvals of merge request !{}", mr_id))
+}
+
+/// Open issues assigned to the user
+fn assigned_issues(args: &clap::ArgMatches, config: &config::Config, gitlabclient: &Client) -> Result<Vec<Value>> {
+    let max = value_t_or_exit!(args, "max", u32);
+
+    let mut i = Issues::builder();
+    i.scope(IssueScope::AssignedToMe).state(IssueState::Opened);
+    let endpoint = issue::list::generate_issues_builder(args, config, &mut i)?;
+
+    api::paged(endpoint, api::Pagination::Limit(max as usize))
+        .query(gitlabclient)
+        .context("Failed to query issues")
+}
+
+/// The user's pending to-do items in the project
+fn pending_todos(args: &clap::ArgMatches, config: &config::Config, gitlabclient: &Client) -> Result<Vec<Value>> {
+    let project_id = utils::get_proj_from_arg_or_conf(args, config)?;
+    let max = value_t_or_exit!(args, "max", u32);
+
+    let endpoint = Todos::builder()
+        .project_id(project_id)
+        .state(TodoState::Pending)
+        .build()
+        .map_err(|e| anyhow!("Could not construct to-do items query.\n {}", e))?;
+
+    api::paged(endpoint, api::Pagination::Limit(max as usize))
+        .query(gitlabclient)
+        .context("Failed to query to-do items")
+}
+
+fn format_date(date: &Value, human: bool) -> String {
+    match date.as_str().map(|d| d.parse::<DateTime<Utc>>()) {
+        Some(Ok(d)) if human => HumanTime::from(d).to_string(),
+        Some(Ok(d)) => DateTime::<Local>::from(d).format("%Y-%m-%d %H:%M:%S").to_string(),
+        _ => "-".to_string(),
+    }
+}
+
+fn new_table(headers: &[&str]) -> Table {
+    let mut table = Table::new();
+
+    table
+        .load_preset("                   ")
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .add_row(headers.iter().map(|h| Cell::new(h).set_alignment(CellAlignment::Center)));
+
+    table
+}
+
+fn print_authored_mrs(mrs: Vec<Value>, human: bool) {
+    let mut table = new_table(&["ID", "TITLE", "PIPELINE", "APPROVALS", "UPDATED ON"]);
+
+    for m in mrs {
+        let pipeline = m["head_pipeline"]["status"].as_str().unwrap_or("-").to_string();
+
+        let approvals = &m["approvals"];
+        let approval_state = match (approvals["approved"].as_bool(), approvals["approvals_left"].as_u64()) {
+            (_, Some(left)) if left > 0 => format!("{} required", left),
+            (Some(true), _) => "approved".to_string(),
+            _ => "-".to_string(),
+        };
+
+        table.add_row(vec![
+            Cell::new(m["iid"].as_u64().unwrap()).set_alignment(CellAlignment::Right),
+            Cell::new(m["title"].as_str().unwrap()),
+            Cell::new(pipeline).set_alignment(CellAlignment::Center),
+            Cell::new(approval_state).set_alignment(CellAlignment::Center),
+            Cell::new(format_date(&m["updated_at"], human)),
+        ]);
+    }
+    println!("{}", table);
+}
+
+fn print_heading(heading: &str, count: usize) {
+    println!("{} {}", heading.bold(), format!("({})", count).dimmed());
+}
+
+pub fn status_cmd(args: clap::ArgMatches, config: config::Config, gitlabclient: Client) -> Result<()> {
+    let user: Map<String, Value> = CurrentUser::builder()
+        .build()
+        .unwrap()
+        .query(&gitlabclient)
+        .context("Failed to find current user")?;
+    let user_id = user["id"].as_u64().unwrap();
+
+    let to_review = review_requests(&args, &config, &gitlabclient, user_id)?;
+    let authored = authored_mrs(&args, &config, &gitlabclient)?;
+    let issues = assigned_issues(&args, &config, &gitlabclient)?;
+    let todos = pending_todos(&args, &config, &gitlabclient)?;
+
+    debug!("args: {:#?}", args);
+
+    match config.format {
+        Some(OutputFormat::JSON) => {
+            let status = json!({
+                "review_requests": to_review,
+                "merge_requests": authored,
+                "issues": issues,
+                "todos": todos,
+            });
+
+            println!("{}", status);
+            Ok(())
+        }
+
+        Some(OutputFormat::Text) => {
+            let human = args.occurrences_of("human_friendly") > 0;
+            let fields = |f: &[&str]| f.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+
+            print_heading("Merge requests to review or approve", to_review.len());
+            if !to_review.is_empty() {
+                mr::list::print_mrs(
+                    serde_json::from_value(Value::Array(to_review))?,
+                    fields(&["id", "title", "author", "updated_on"]),
+                    false,
+                    human,
+                );
+            }
+            println!();
+
+            print_heading("My merge requests", authored.len());
+            if !authored.is_empty() {
+                print_authored_mrs(authored, human);
+            }
+            println!();
+
+            print_heading("Issues assigned to me", issues.len());
+            if !issues.is_empty() {
+                issue::list::print_issues(
+                    serde_json::from_value(Value::Array(issues))?,
+                    fields(&["id", "title", "labels", "updated_on"]),
+                    false,
+                    human,
+                );
+            }
+            println!();
+
+            print_heading("To-do items", todos.len());
+            if !todos.is_empty() {
+                todo::list::print_todos(
+                    serde_json::from_value(Value::Array(todos))?,
+                    fields(&["target", "action", "author", "title"]),
+                    false,
+                    human,
+                );
+            }
+            Ok(())
+        }
+        _ => Err(anyhow!("Bad output format in config")),
+    }
+}
+
+#[cfg(test)]
+mod status_unit_tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest(
+        approved, eligible, approved_by, expected,
+        case(false, vec![1, 2], vec![], true),
+        case(false, vec![2], vec![], false),
+        case(false, vec![1, 2], vec![1], false),
+        case(true, vec![1, 2], vec![2], false),
+    )]
+    fn test_can_approve(approved: bool, eligible: Vec<u64>, approved_by: Vec<u64>, expected: bool) {
+        let users = |ids: Vec<u64>| ids.into_iter().map(|id| json!({ "id": id })).collect::<Vec<Value>>();
+        let state = json!({
+            "rules": [
+                { "approved": approved, "eligible_approvers": users(eligible), "approved_by": users(approved_by) },
+            ],
+        });
+
+        assert_eq!(can_approve(&state, 1), expected);
+    }
+
+    #[test]
+    fn test_can_approve_no_rules() {
+        assert!(!can_approve(&json!({}), 1));
+    }
+}
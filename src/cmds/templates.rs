@@ -0,0 +1,217 @@
+//! Description templates, which GitLab projects keep as markdown files in `.gitlab/issue_templates`
+//! and `.gitlab/merge_request_templates`, so these are shared by the `issue` and `mr` commands.
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context, Result};
+use dialoguer::Select;
+use serde::Deserialize;
+
+use crate::config;
+use crate::gitlab::Project as GLProject;
+use crate::gitlab::{api, Client, Query, RawFile, RepositoryTree};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TemplateType {
+    Issue,
+    MergeRequest,
+}
+
+impl TemplateType {
+    /// The directory in the repository holding the templates
+    fn dir(self) -> &'static str {
+        match self {
+            TemplateType::Issue => ".gitlab/issue_templates",
+            TemplateType::MergeRequest => ".gitlab/merge_request_templates",
+        }
+    }
+}
+
+/// Name of the template GitLab applies when no other is chosen
+const DEFAULT_TEMPLATE: &str = "Default";
+
+/// The `--template` argument for commands that take a description
+pub fn template_arg<'a>() -> clap::Arg<'a, 'a> {
+    clap::Arg::with_name("template")
+        .long("template")
+        .short("T")
+        .help("Name of the description template to start from, out of those in the project's .gitlab directory")
+        .takes_value(true)
+        .empty_values(false)
+}
+
+/// Return the local templates directory, if the templates can be read from the local repo rather
+/// than through the API
+fn local_template_dir(kind: TemplateType, project_id: u64, config: &config::Config) -> Option<PathBuf> {
+    match (&config.repo_path, config.projectid) {
+        (Some(repo_path), Some(p)) if p == project_id => {
+            let dir = repo_path.join(kind.dir());
+            if dir.is_dir() { Some(dir) } else { None }
+        },
+        _ => None,
+    }
+}
+
+/// Return the names of the templates, without the `.md` extension, in alphabetical order
+fn template_names(kind: TemplateType, project_id: u64, config: &config::Config, gitlabclient: &Client) -> Vec<String> {
+    let files = match local_template_dir(kind, project_id, config) {
+        Some(dir) => fs::read_dir(dir)
+            .map(|entries| entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_file())
+                .filter_map(|e| e.file_name().into_string().ok())
+                .collect::<Vec<String>>())
+            .unwrap_or_default(),
+
+        None => {
+            #[derive(Deserialize, Debug)]
+            struct TreeEntry { name: String, #[serde(rename = "type")] type_: String }
+
+            RepositoryTree::builder()
+                .project(project_id)
+                .path(kind.dir())
+                .build()
+                .ok()
+                .and_then(|endpoint| api::paged(endpoint, api::Pagination::All)
+                    .query(gitlabclient)
+                    .ok())
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|e: &TreeEntry| e.type_ == "blob")
+                .map(|e| e.name)
+                .collect()
+        },
+    };
+
+    let mut names = files
+        .into_iter()
+        .filter_map(|f| f.strip_suffix(".md").map(|n| n.to_string()))
+        .collect::<Vec<String>>();
+    names.sort();
+
+    debug!("{:?} templates: {:?}", kind, names);
+    names
+}
+
+fn template_content(
+    kind: TemplateType,
+    name: &str,
+    project_id: u64,
+    config: &config::Config,
+    gitlabclient: &Client,
+) -> Result<String> {
+    let file = format!("{}.md", name);
+
+    match local_template_dir(kind, project_id, config) {
+        Some(dir) => fs::read_to_string(dir.join(&file))
+            .with_context(|| format!("Failed to read template {}", name)),
+
+        None => {
+            let endpoint = RawFile::builder()
+                .project(project_id)
+                .file_path(format!("{}/{}", kind.dir(), file))
+                .build()
+                .map_err(|e| anyhow!("Could not construct API call to get template.\n {}", e))?;
+
+            let raw = api::raw(endpoint)
+                .query(gitlabclient)
+                .with_context(|| format!("Failed to get template {}", name))?;
+
+            String::from_utf8(raw)
+                .with_context(|| format!("Template {} is not valid UTF-8", name))
+        },
+    }
+}
+
+/// Return the description template set in the project's settings, if there is one
+fn project_default_template(kind: TemplateType, project_id: u64, gitlabclient: &Client) -> Option<String> {
+    #[derive(Deserialize, Debug)]
+    struct Project {
+        issues_template: Option<String>,
+        merge_requests_template: Option<String>,
+    }
+
+    let endpoint = GLProject::builder().project(project_id).build().ok()?;
+    let project: Project = endpoint.query(gitlabclient).ok()?;
+
+    match kind {
+        TemplateType::Issue => project.issues_template,
+        TemplateType::MergeRequest => project.merge_requests_template,
+    }
+    .filter(|t| !t.trim().is_empty())
+}
+
+/// Find a template by name, ignoring case and any `.md` extension
+fn find_template<'a>(names: &'a [String], name: &str) -> Option<&'a String> {
+    let name = name.strip_suffix(".md").unwrap_or(name);
+    names.iter().find(|n| n.eq_ignore_ascii_case(name))
+}
+
+/// Return the description template to start from: the one passed with `--template`, otherwise the
+/// project's default one, otherwise one chosen by the user out of those available, if any
+pub fn get_template(
+    args: &clap::ArgMatches,
+    kind: TemplateType,
+    project_id: u64,
+    config: &config::Config,
+    gitlabclient: &Client,
+) -> Result<Option<String>> {
+    let names = template_names(kind, project_id, config, gitlabclient);
+
+    if let Some(name) = args.value_of("template") {
+        let found = find_template(&names, name)
+            .ok_or_else(|| match names.is_empty() {
+                true => anyhow!("Template {} not found - the project has no templates", name),
+                false => anyhow!("Template {} not found - choose from: {}", name, names.join(", ")),
+            })?;
+        return template_content(kind, found, project_id, config, gitlabclient).map(Some)
+    }
+
+    // the default template in the project's settings takes precedence over a Default.md
+    if let Some(t) = project_default_template(kind, project_id, gitlabclient) {
+        return Ok(Some(t))
+    }
+
+    if let Some(name) = find_template(&names, DEFAULT_TEMPLATE) {
+        return template_content(kind, name, project_id, config, gitlabclient).map(Some)
+    }
+
+    if names.is_empty() {
+        return Ok(None)
+    }
+
+    let mut items = vec!["No template".to_string()];
+    items.extend(names.iter().cloned());
+
+    let selected = Select::new()
+        .with_prompt("Description template")
+        .items(&items)
+        .default(0)
+        .interact()?;
+
+    match selected {
+        0 => Ok(None),
+        i => template_content(kind, &names[i - 1], project_id, config, gitlabclient).map(Some),
+    }
+}
+
+#[cfg(test)]
+mod templates_unit_tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest(
+        name, expected,
+        case("Bug", Some("Bug")),
+        case("bug", Some("Bug")),
+        case("Bug.md", Some("Bug")),
+        case("default", Some("Default")),
+        case("Feature", None),
+    )]
+    fn test_find_template(name: &str, expected: Option<&str>) {
+        let names = vec!["Bug".to_string(), "Default".to_string()];
+
+        assert_eq!(find_template(&names, name).map(|n| n.as_str()), expected);
+    }
+}
@@ -0,0 +1,247 @@
+//! Time tracking, which works the same way for issues and merge requests, so the `time`
+//! subcommand is shared by the `issue` and `mr` commands.
+use anyhow::{anyhow, Context, Result};
+use clap::value_t_or_exit;
+use serde::Deserialize;
+
+use crate::config;
+use crate::config::OutputFormat;
+use crate::gitlab::api::Endpoint;
+use crate::gitlab::TimeStats as GLTimeStats;
+use crate::gitlab::{
+    api, AddSpentTime, Client, Query, ResetSpentTime, ResetTimeEstimate,
+    SetTimeEstimate, TimeTrackable,
+};
+use crate::utils;
+use crate::utils::validator;
+
+#[derive(Debug, Deserialize)]
+pub struct TimeStats {
+    time_estimate: u64,
+    total_time_spent: u64,
+    human_time_estimate: Option<String>,
+    human_total_time_spent: Option<String>,
+}
+
+fn id_help(kind: TimeTrackable) -> &'static str {
+    match kind {
+        TimeTrackable::Issue => "Issue ID",
+        TimeTrackable::MergeRequest => "Merge request ID",
+    }
+}
+
+fn project_id_arg<'a>(kind: TimeTrackable) -> clap::Arg<'a, 'a> {
+    utils::project_id_arg(match kind {
+        TimeTrackable::Issue => "Project ID to look for issue in. Defaults to attached Project ID.",
+        TimeTrackable::MergeRequest => "Project ID to look for merge request in. Defaults to attached Project ID.",
+    })
+}
+
+fn id_arg<'a>(kind: TimeTrackable) -> clap::Arg<'a, 'a> {
+    clap::Arg::with_name("id")
+        .help(id_help(kind))
+        .takes_value(true)
+        .empty_values(false)
+        .required(true)
+        .validator(validator::check_u64)
+}
+
+fn duration_arg<'a>(help: &'a str) -> clap::Arg<'a, 'a> {
+    clap::Arg::with_name("duration")
+        .help(help)
+        .takes_value(true)
+        .empty_values(false)
+        .required(true)
+        .validator(validator::check_valid_humantime_duration)
+}
+
+/// Generates the `time` subcommand for issues or merge requests.
+pub fn gen_time_clap_command<'a>(kind: TimeTrackable) -> clap::App<'a, 'a> {
+    clap::SubCommand::with_name("time")
+        .about("Tracks time spent and estimated")
+        .setting(clap::AppSettings::ColoredHelp)
+        .setting(clap::AppSettings::VersionlessSubcommands)
+        .setting(clap::AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            clap::SubCommand::with_name("add")
+                .about("Adds time spent")
+                .setting(clap::AppSettings::ColoredHelp)
+                .arg(id_arg(kind))
+                .arg(duration_arg("Time spent, e.g., 1h30m"))
+                .arg(
+                    clap::Arg::with_name("summary")
+                        .long("summary")
+                        .short("s")
+                        .help("Summary of the work done")
+                        .takes_value(true)
+                        .empty_values(false)
+                )
+                .arg(
+                    clap::Arg::with_name("subtract")
+                        .long("subtract")
+                        .help("Subtracts the time instead, e.g., to correct a mistake")
+                )
+                .arg(project_id_arg(kind))
+                .after_help(
+"Durations are interpreted by GitLab, where a day is 8 hours, a week is 5 days and a month is 4 \
+weeks.",
+                ),
+        )
+        .subcommand(
+            clap::SubCommand::with_name("estimate")
+                .about("Sets the time estimate")
+                .setting(clap::AppSettings::ColoredHelp)
+                .arg(id_arg(kind))
+                .arg(duration_arg("Estimated time, e.g., 3h"))
+                .arg(project_id_arg(kind))
+                .after_help(
+"Durations are interpreted by GitLab, where a day is 8 hours, a week is 5 days and a month is 4 \
+weeks.",
+                ),
+        )
+        .subcommand(
+            clap::SubCommand::with_name("reset")
+                .about("Resets the time spent to zero")
+                .setting(clap::AppSettings::ColoredHelp)
+                .arg(id_arg(kind))
+                .arg(
+                    clap::Arg::with_name("estimate")
+                        .long("estimate")
+                        .short("e")
+                        .help("Removes the time estimate instead")
+                )
+                .arg(project_id_arg(kind))
+        )
+        .subcommand(
+            clap::SubCommand::with_name("stats")
+                .about("Shows the time spent and estimated")
+                .setting(clap::AppSettings::ColoredHelp)
+                .arg(id_arg(kind))
+                .arg(project_id_arg(kind))
+        )
+}
+
+fn print_time_stats(stats: TimeStats) {
+    let estimate = match (stats.time_estimate, stats.human_time_estimate) {
+        (0, _) | (_, None) => "-".to_string(),
+        (_, Some(e)) => e,
+    };
+    let spent = match (stats.total_time_spent, stats.human_total_time_spent) {
+        (0, _) | (_, None) => "-".to_string(),
+        (_, Some(s)) => s,
+    };
+
+    println!("Estimate:   {}", estimate);
+    println!("Time spent: {}", spent);
+}
+
+fn query_time_stats<E: Endpoint>(endpoint: E, config: &config::Config, gitlabclient: &Client) -> Result<()> {
+    match config.format {
+        Some(OutputFormat::JSON) => {
+            let raw_json = api::raw(endpoint)
+                .query(gitlabclient)
+                .context("Failed to update time tracking")?;
+
+            println!("{}", String::from_utf8(raw_json).unwrap());
+            Ok(())
+        }
+
+        Some(OutputFormat::Text) => {
+            let stats: TimeStats = endpoint
+                .query(gitlabclient)
+                .context("Failed to update time tracking")?;
+
+            print_time_stats(stats);
+            Ok(())
+        }
+        _ => Err(anyhow!("Bad output format in config")),
+    }
+}
+
+/// Runs the `time` subcommand for issues or merge requests.
+pub fn time_cmd(
+    args: clap::ArgMatches,
+    kind: TimeTrackable,
+    config: config::Config,
+    gitlabclient: Client,
+) -> Result<()> {
+    let (subcmd, args) = match args.subcommand() {
+        (s, Some(a)) => (s, a),
+        _ => unreachable!(),
+    };
+
+    let project_id = utils::get_proj_from_arg_or_conf(args, &config)?;
+    let iid = value_t_or_exit!(args, "id", u64);
+
+    debug!("args: {:#?}", args);
+
+    match subcmd {
+        "add" => {
+            let duration = if args.is_present("subtract") {
+                format!("-{}", args.value_of("duration").unwrap())
+            } else {
+                args.value_of("duration").unwrap().to_string()
+            };
+
+            let mut t = AddSpentTime::builder();
+            t.project(project_id).kind(kind).iid(iid).duration(duration);
+
+            if let Some(summary) = args.value_of("summary") {
+                t.summary(summary);
+            }
+
+            let endpoint = t
+                .build()
+                .map_err(|e| anyhow!("Could not construct time tracking query.\n {}", e))?;
+            debug!("endpoint: {:#?}", endpoint);
+
+            query_time_stats(endpoint, &config, &gitlabclient)
+        },
+        "estimate" => {
+            let endpoint = SetTimeEstimate::builder()
+                .project(project_id)
+                .kind(kind)
+                .iid(iid)
+                .duration(args.value_of("duration").unwrap())
+                .build()
+                .map_err(|e| anyhow!("Could not construct time tracking query.\n {}", e))?;
+            debug!("endpoint: {:#?}", endpoint);
+
+            query_time_stats(endpoint, &config, &gitlabclient)
+        },
+        "reset" if args.is_present("estimate") => {
+            let endpoint = ResetTimeEstimate::builder()
+                .project(project_id)
+                .kind(kind)
+                .iid(iid)
+                .build()
+                .map_err(|e| anyhow!("Could not construct time tracking query.\n {}", e))?;
+            debug!("endpoint: {:#?}", endpoint);
+
+            query_time_stats(endpoint, &config, &gitlabclient)
+        },
+        "reset" => {
+            let endpoint = ResetSpentTime::builder()
+                .project(project_id)
+                .kind(kind)
+                .iid(iid)
+                .build()
+                .map_err(|e| anyhow!("Could not construct time tracking query.\n {}", e))?;
+            debug!("endpoint: {:#?}", endpoint);
+
+            query_time_stats(endpoint, &config, &gitlabclient)
+        },
+        "stats" => {
+            let endpoint = GLTimeStats::builder()
+                .project(project_id)
+                .kind(kind)
+                .iid(iid)
+                .build()
+                .map_err(|e| anyhow!("Could not construct time tracking query.\n {}", e))?;
+            debug!("endpoint: {:#?}", endpoint);
+
+            query_time_stats(endpoint, &config, &gitlabclient)
+        },
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::{anyhow, Context, Result};
+use clap::values_t_or_exit;
+
+use crate::config;
+use crate::config::OutputFormat;
+use crate::gitlab::{api, Client, MarkAllTodosAsDone, MarkTodoAsDone, Query};
+
+pub fn done_todos_cmd(
+    args: clap::ArgMatches,
+    config: config::Config,
+    gitlabclient: Client,
+) -> Result<()> {
+    debug!("args: {:#?}", args);
+
+    for todo_id in values_t_or_exit!(args, "ids", u64) {
+        let endpoint = MarkTodoAsDone::builder()
+            .todo(todo_id)
+            .build()
+            .map_err(|e| anyhow!("Could not construct to-do item query.\n {}", e))?;
+
+        debug!("endpoint: {:#?}", endpoint);
+
+        match config.format {
+            Some(OutputFormat::JSON) => {
+                let raw_json = api::raw(endpoint)
+                    .query(&gitlabclient)
+                    .with_context(|| format!("Failed to mark to-do item {} as done", todo_id))?;
+
+                println!("{}", String::from_utf8(raw_json).unwrap());
+            }
+
+            Some(OutputFormat::Text) => {
+                api::ignore(endpoint)
+                    .query(&gitlabclient)
+                    .with_context(|| format!("Failed to mark to-do item {} as done", todo_id))?;
+            }
+            _ => return Err(anyhow!("Bad output format in config")),
+        }
+    }
+
+    Ok(())
+}
+
+pub fn done_all_todos_cmd(
+    args: clap::ArgMatches,
+    _config: config::Config,
+    gitlabclient: Client,
+) -> Result<()> {
+    let endpoint = MarkAllTodosAsDone::builder()
+        .build()
+        .map_err(|e| anyhow!("Could not construct to-do items query.\n {}", e))?;
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    api::ignore(endpoint)
+        .query(&gitlabclient)
+        .context("Failed to mark to-do items as done")?;
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Local};
+use chrono_humanize::HumanTime;
+use clap::{value_t_or_exit, values_t_or_exit};
+use comfy_table::*;
+
+use crate::config;
+use crate::config::OutputFormat;
+use crate::gitlab::converter::{
+    todo_action_from_str, todo_state_from_str, todo_target_type_from_str,
+};
+use crate::gitlab::{api, Client, Query, Todos, TodosBuilder};
+use crate::cmds::todo::Todo;
+
+pub fn generate_todos_builder<'a>(
+    args: &'a clap::ArgMatches,
+    t: &'a mut TodosBuilder,
+) -> Result<Todos> {
+
+    for arg in &args.args {
+        let (key, _) = arg;
+        match *key {
+            "state" => t.state(todo_state_from_str(args.value_of("state").unwrap()).unwrap()),
+            "action" => t.action(todo_action_from_str(args.value_of("action").unwrap()).unwrap()),
+            "type" => t.target_type(todo_target_type_from_str(args.value_of("type").unwrap()).unwrap()),
+            "project_id" => t.project_id(value_t_or_exit!(args, "project_id", u64)),
+            // presentational arguments
+            "max" => t,
+            "fields" => t,
+            "no_headers" => t,
+            "human_friendly" => t,
+            _ => unreachable!(),
+        };
+    }
+    t.build()
+        .map_err(|e| anyhow!("Could not construct to-do items query.\n {}", e))
+}
+
+/// The issue or merge request that a to-do item is for, e.g., `group/project!12`
+fn target_reference(t: &Todo) -> String {
+    match (t.target["references"]["full"].as_str(), t.target["iid"].as_u64(), t.target_type.as_str()) {
+        (Some(r), _, _) => r.to_string(),
+        (None, Some(iid), "MergeRequest") => format!("!{}", iid),
+        (None, Some(iid), "Issue") => format!("#{}", iid),
+        _ => "-".to_string(),
+    }
+}
+
+pub fn print_todos(todos: Vec<Todo>, fields: Vec<String>, no_headers: bool, human: bool) {
+    let mut table = Table::new();
+
+    table
+        .load_preset("                   ")
+        .set_content_arrangement(ContentArrangement::Dynamic);
+
+    if !no_headers {
+        table.add_row(fields.iter().map(|f| Cell::new(f.to_uppercase().replace("_"," ")).set_alignment(CellAlignment::Center)));
+    }
+
+    for t in todos {
+        let mut r: Vec<Cell> =Vec::new();
+
+        for field in &fields {
+            match field.as_str() {
+                "action" => r.push(Cell::new(t.action_name.replace("_", " "))),
+                "author" => r.push(Cell::new(t.author["username"].as_str().unwrap())),
+                "created_on" =>
+                        if human {
+                            r.push(Cell::new(HumanTime::from(t.created_at)))
+                        } else {
+                            let d: DateTime<Local> = DateTime::from(t.created_at);
+                            r.push(Cell::new(d.format("%Y-%m-%d %H:%M:%S").to_string()))
+                        }
+                "id" => r.push(Cell::new(t.id).set_alignment(CellAlignment::Right)),
+                "project" => {
+                    match &t.project {
+                        Some(p) => r.push(Cell::new(p["path_with_namespace"].as_str().unwrap())),
+                        None => r.push(Cell::new("-").set_alignment(CellAlignment::Center)),
+                    }
+                },
+                "state" => r.push(Cell::new(t.state.clone())),
+                "target" => r.push(Cell::new(target_reference(&t))),
+                "title" => r.push(Cell::new(t.target["title"].as_str().unwrap_or(&t.body))),
+                "type" => {
+                    match t.target_type.as_str() {
+                        "MergeRequest" => r.push(Cell::new("mr")),
+                        "Issue" => r.push(Cell::new("issue")),
+                        other => r.push(Cell::new(other)),
+                    }
+                },
+                _ => unreachable!(""),
+            }
+        }
+
+        if t.state == "done" {
+            r = r.iter().map(|f| f.clone().add_attribute(Attribute::Dim)).collect();
+        }
+
+        table.add_row(r);
+    }
+    println!("{}", table);
+}
+
+
+pub fn list_todos_cmd(
+    args: clap::ArgMatches,
+    config: config::Config,
+    gitlabclient: Client,
+) -> Result<()> {
+    let mut t = Todos::builder();
+    let endpoint = generate_todos_builder(&args, &mut t)?;
+    let max = value_t_or_exit!(args, "max", u32);
+
+    debug!("args: {:#?}", args);
+    debug!("endpoint: {:#?}", endpoint);
+
+    match config.format {
+        Some(OutputFormat::JSON) => {
+            let raw_json = api::raw(endpoint)
+                .query(&gitlabclient)
+                .context("Failed to query to-do items")?;
+
+            println!("{}", String::from_utf8(raw_json).unwrap());
+            Ok(())
+        }
+
+        Some(OutputFormat::Text) => {
+            let todos: Vec<Todo> = api::paged(endpoint, api::Pagination::Limit(max as usize))
+                .query(&gitlabclient)
+                .context("Failed to query to-do items")?;
+
+            print_todos(
+                todos,
+                values_t_or_exit!(args, "fields", String),
+                args.occurrences_of("no_headers")>0,
+                args.occurrences_of("human_friendly")>0
+                );
+
+            Ok(())
+        }
+        _ => Err(anyhow!("Bad output format in config")),
+    }
+}
@@ -0,0 +1,171 @@
+mod done;
+pub mod list;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::config;
+use crate::gitlab;
+use crate::subcommand;
+use crate::utils::validator;
+
+#[derive(Debug, Deserialize)]
+pub struct Todo {
+    id: u64,
+    project: Option<Map<String, Value>>,
+    author: Map<String, Value>,
+    action_name: String,
+    target_type: String,
+    target: Map<String, Value>,
+    body: String,
+    state: String,
+    created_at: DateTime<Utc>,
+}
+
+/// This implements the `todo` command. It provides the ability to list the user's to-do items and
+/// mark them as done.
+pub struct TodoCmd<'a> {
+    pub clap_cmd: clap::App<'a, 'a>,
+}
+
+impl subcommand::SubCommand for TodoCmd<'_> {
+    fn gen_clap_command(&self) -> clap::App {
+        let c = self.clap_cmd.clone();
+        c.about("Lists and clears to-do items")
+            .setting(clap::AppSettings::ColoredHelp)
+            .setting(clap::AppSettings::VersionlessSubcommands)
+            .setting(clap::AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                clap::SubCommand::with_name("list")
+                    .about("Lists to-do items")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("state")
+                            .long("state")
+                            .short("s")
+                            .help("Filter to-do items by state")
+                            .takes_value(true)
+                            .possible_values(&["pending", "done"])
+                            .default_value("pending")
+                    )
+                    .arg(
+                        clap::Arg::with_name("action")
+                            .long("action")
+                            .short("a")
+                            .help("Filter to-do items by the action which created them")
+                            .takes_value(true)
+                            .possible_values(&[
+                                "assigned",
+                                "mentioned",
+                                "build_failed",
+                                "marked",
+                                "approval_required",
+                                "unmergeable",
+                                "directly_addressed",
+                                "review_requested",
+                            ])
+                    )
+                    .arg(
+                        clap::Arg::with_name("type")
+                            .long("type")
+                            .short("t")
+                            .help("Filter to-do items by the type of their target")
+                            .takes_value(true)
+                            .possible_values(&["issue", "mr"])
+                    )
+                    .arg(
+                        clap::Arg::with_name("project_id")
+                            .long("project_id")
+                            .short("p")
+                            .help("Filter to-do items by project ID")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .validator(validator::check_u64)
+                    )
+                    .arg(
+                        clap::Arg::with_name("fields")
+                            .long("fields")
+                            .short("F")
+                            .help("Specify which fields to output")
+                            .takes_value(true)
+                            .multiple(true)
+                            .require_delimiter(true)
+                            .possible_values(
+                                &[
+                                "action",
+                                "author",
+                                "created_on", // created_at
+                                "id",
+                                "project",
+                                "state",
+                                "target",
+                                "title",
+                                "type",
+                                ])
+                            .default_value("id,target,action,author,title")
+                    )
+                    .arg(
+                        clap::Arg::with_name("no_headers")
+                            .long("no_headers")
+                            .help("Suppress header row on text output")
+                    )
+                    .arg(
+                        clap::Arg::with_name("human_friendly")
+                            .short("h")
+                            .help("Use human-friendly date-time strings")
+                    )
+                    .arg(
+                        clap::Arg::with_name("max")
+                            .long("max")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .default_value("40")
+                            .help("Maximum records to return")
+                            .validator(validator::check_u32)
+                    )
+                    .after_help(
+"Unlike most other list commands, this lists to-do items across all projects unless `--project_id` \
+is passed."
+                    ),
+            )
+            .subcommand(
+                clap::SubCommand::with_name("done")
+                    .about("Marks to-do items as done")
+                    .setting(clap::AppSettings::ColoredHelp)
+                    .arg(
+                        clap::Arg::with_name("ids")
+                            .help("To-do item ID(s)")
+                            .takes_value(true)
+                            .empty_values(false)
+                            .multiple(true)
+                            .require_delimiter(true)
+                            .required(true)
+                            .validator(validator::check_u64)
+                    )
+            )
+            .subcommand(
+                clap::SubCommand::with_name("done-all")
+                    .about("Marks all pending to-do items as done")
+                    .setting(clap::AppSettings::ColoredHelp)
+            )
+    }
+
+    fn run(&self, config: config::Config, args: clap::ArgMatches) -> Result<()> {
+
+        trace!("Config: {:?}", config);
+        debug!("Args: {:#?}", args);
+
+        let gitlabclient = gitlab::new(&config).context("Could not create GitLab client connection.")?;
+
+        match args.subcommand() {
+            ("done", Some(a)) => done::done_todos_cmd(a.clone(), config, *gitlabclient)?,
+            ("done-all", Some(a)) => done::done_all_todos_cmd(a.clone(), config, *gitlabclient)?,
+            ("list", Some(a)) => list::list_todos_cmd(a.clone(), config, *gitlabclient)?,
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+}
@@ -77,8 +77,12 @@ pub struct Config {
     pub user_config_type: Option<UserGitConfigLevel>, //convenience param, not saved with ::save()
     pub projectid: Option<u64>, //set with project attach command
     pub defaultbranch: Option<String>, //set with project attach command
+    pub remote: Option<String>, //git remote pointing at the attached project, set with project attach command
     pub labels: Vec<String>, //project labels for attached project
     pub members: Vec<String>, //project members formatted as "id:username"
+    pub fork_project_id: Option<u64>, //set with project fork command
+    pub fork_remote: Option<String>, //set with project fork command
+    pub draft_prefix: Option<String>, //title prefix marking a merge request as a draft
 }
 
 /// Open System, XDG and Global multi-level config or return empty config.
@@ -133,7 +137,11 @@ fn update_config_from_git(config: &mut Config, git_config: &GitConfig) {
             "gitlab.label" =>  config.labels.push(entry.value().unwrap().to_string()),
             "gitlab.member" =>  config.members.push(entry.value().unwrap().to_string()),
             "gitlab.defaultbranch" => config.defaultbranch = Some(entry.value().unwrap().to_string()),
+            "gitlab.remote" => config.remote = Some(entry.value().unwrap().to_string()),
             "gitlab.pathwithnamespace" => config.path_with_namespace = Some(entry.value().unwrap().to_string()),
+            "gitlab.forkprojectid" => config.fork_project_id = Some(entry.value().unwrap().parse::<u64>().unwrap()),
+            "gitlab.forkremote" => config.fork_remote = Some(entry.value().unwrap().to_string()),
+            "gitlab.draftprefix" => config.draft_prefix = Some(entry.value().unwrap().to_string()),
             _ => (),
         };
         trace!(
@@ -166,6 +174,7 @@ where
         };
         if key == "GITLABCLI_FORMAT" { config.format = value.parse::<OutputFormat>().ok(); continue };
         if key == "GITLABCLI_PROJECTID" { config.projectid = value.parse::<u64>().ok(); continue };
+        if key == "GITLABCLI_REMOTE" { config.remote = Some(value); continue };
     }
 }
 
@@ -272,6 +281,30 @@ fn write_config(save_config: &mut GitConfig, config: &Config) -> Result<()> {
             .context("Failed to save gitlab.defaultbranch to git config.")?;
     }
 
+    if config.remote.is_some()
+        && ( env::var("GITLABCLI_REMOTE").is_err()
+            || &env::var("GITLABCLI_REMOTE").unwrap() != config.remote.as_ref().unwrap()
+           )
+    {
+        save_config.set_str("gitlab.remote", config.remote.as_ref().unwrap())
+            .context("Failed to save gitlab.remote to git config.")?;
+    }
+
+    if config.fork_project_id.is_some() {
+        save_config.set_i64("gitlab.forkprojectid", i64::try_from(config.fork_project_id.unwrap()).unwrap())
+            .context("Failed to save gitlab.forkprojectid to git config.")?;
+    }
+
+    if config.fork_remote.is_some() {
+        save_config.set_str("gitlab.forkremote", config.fork_remote.as_ref().unwrap())
+            .context("Failed to save gitlab.forkremote to git config.")?;
+    }
+
+    if config.draft_prefix.is_some() {
+        save_config.set_str("gitlab.draftprefix", config.draft_prefix.as_ref().unwrap())
+            .context("Failed to save gitlab.draftprefix to git config.")?;
+    }
+
     Ok(())
 }
 
@@ -290,7 +323,11 @@ impl Config {
             labels: vec!(),
             members: vec!(),
             defaultbranch: None,
+            remote: None,
             path_with_namespace: None,
+            fork_project_id: None,
+            fork_remote: None,
+            draft_prefix: None,
         }
     }
 
@@ -543,12 +580,14 @@ mod config_unit_tests {
         git_config.set_str("gitlab.host", "some.host.name").unwrap();
         git_config.set_bool("gitlab.tls", true).unwrap();
         git_config.set_str("gitlab.format", "json").unwrap();
+        git_config.set_str("gitlab.draftprefix", "[Draft]").unwrap();
         let mut config = Config::new();
         cd_repo();
 
         update_config_from_git(&mut config, &git_config);
 
         assert_eq!(config.token.unwrap(), "testtoken");
+        assert_eq!(config.draft_prefix.unwrap(), "[Draft]");
         assert_eq!(config.host.unwrap(), "some.host.name");
         assert_eq!(config.format.unwrap(),OutputFormat::JSON);
         assert!(config.tls.unwrap());
@@ -671,11 +710,13 @@ mod config_unit_tests {
         env.insert("GITLABCLI_HOST".to_string(), "env_host".to_string());
         env.insert("GITLABCLI_TLS".to_string(), "yeS".to_string());
         env.insert("GITLABCLI_FORMAT".to_string(), "Json".to_string());
+        env.insert("GITLABCLI_REMOTE".to_string(), "env_remote".to_string());
 
         update_config_from_env(&mut conf, env.into_iter());
 
         assert_eq!(conf.token.unwrap(), "env_token");
         assert_eq!(conf.host.unwrap(), "env_host");
+        assert_eq!(conf.remote.unwrap(), "env_remote");
         assert_eq!(conf.format.unwrap(), OutputFormat::JSON);
         assert!(conf.tls.unwrap());
     }
@@ -697,10 +738,14 @@ mod config_unit_tests {
             projectid: Some(42),
             repo_path: None,
             defaultbranch: None,
+            remote: Some("upstream".to_string()),
             path_with_namespace: None,
             user_config_type: None,
             labels: vec!(),
             members: vec!(),
+            fork_project_id: None,
+            fork_remote: None,
+            draft_prefix: None,
         };
 
         write_config(&mut git_config, &conf).unwrap();
@@ -710,6 +755,7 @@ mod config_unit_tests {
         assert_eq!(git_config.get_string("gitlab.projectid").unwrap(), "42");
         assert!(!git_config.get_bool("gitlab.tls").unwrap());
         assert_eq!(git_config.get_string("gitlab.format").unwrap(), "json");
+        assert_eq!(git_config.get_string("gitlab.remote").unwrap(), "upstream");
 
         reset_global_config();
         reset_xdg_config();
@@ -732,10 +778,14 @@ mod config_unit_tests {
             projectid: Some(42),
             repo_path: None,
             defaultbranch: None,
+            remote: None,
             path_with_namespace: None,
             user_config_type: None,
             labels: vec!(),
             members: vec!(),
+            fork_project_id: None,
+            fork_remote: None,
+            draft_prefix: None,
         };
 
         // delete the whole repo
@@ -764,10 +814,14 @@ mod config_unit_tests {
             projectid: Some(42),
             repo_path: None,
             defaultbranch: None,
+            remote: None,
             path_with_namespace: None,
             user_config_type: None,
             labels: vec!(),
             members: vec!(),
+            fork_project_id: None,
+            fork_remote: None,
+            draft_prefix: None,
         };
 
         write_config(&mut git_config, &conf).unwrap();
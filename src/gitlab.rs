@@ -4,9 +4,19 @@
 //! this as possible, in order to keep the shim as thin as possible.
 //!
 //! Where possible it will just re-export types from the 3rd party library when nothing special
-//! needs to be abstracted.
+//! needs to be abstracted. Endpoints that the 3rd party library does not provide are implemented
+//! in submodules of this one, in the same style as the library's own, so that they can be swapped
+//! out for upstream versions as and when these become available.
 use anyhow::{Context, Result, anyhow};
 
+mod branches;
+mod issues;
+mod merge_requests;
+mod projects;
+mod repository;
+mod time_tracking;
+mod todos;
+
 pub use gitlab::Gitlab as Client;
 pub use gitlab::api as api;
 pub use gitlab::api::Query;
@@ -14,12 +24,26 @@ pub use gitlab::api::projects::Project;
 pub use gitlab::api::projects::ProjectBuilder;
 pub use gitlab::api::projects::CreateProject;
 pub use gitlab::api::projects::CreateProjectBuilder;
+pub use gitlab::api::projects::Projects;
+pub use gitlab::api::projects::ProjectsBuilder;
+pub use gitlab::api::projects::ProjectOrderBy;
+pub use self::projects::ForkProject;
+pub use self::projects::ProjectApprovalRules;
+pub use self::projects::CreateProjectApprovalRule;
 pub use gitlab::api::projects::issues::Issue;
 pub use gitlab::api::projects::issues::IssueBuilder;
 pub use gitlab::api::projects::issues::Issues;
 pub use gitlab::api::projects::issues::IssuesBuilder;
 pub use gitlab::api::projects::issues::EditIssue;
 pub use gitlab::api::projects::issues::EditIssueBuilder;
+pub use self::issues::UpdateIssue;
+pub use self::issues::UpdateIssueBuilder;
+pub use self::issues::IssueLinks;
+pub use self::issues::IssueLinkType;
+pub use self::issues::CreateIssueLink;
+pub use self::issues::DeleteIssueLink;
+pub use self::issues::RelatedMergeRequests;
+pub use self::issues::MergeRequestsClosingIssue;
 pub use gitlab::api::projects::issues::CreateIssue;
 pub use gitlab::api::projects::issues::CreateIssueBuilder;
 pub use gitlab::api::projects::issues::IssueState;
@@ -34,6 +58,12 @@ pub use gitlab::api::projects::merge_requests::MergeRequests;
 pub use gitlab::api::projects::merge_requests::MergeRequestsBuilder;
 pub use gitlab::api::projects::merge_requests::EditMergeRequest;
 pub use gitlab::api::projects::merge_requests::EditMergeRequestBuilder;
+pub use self::merge_requests::UpdateMergeRequest;
+pub use self::merge_requests::UpdateMergeRequestBuilder;
+pub use self::merge_requests::MergeRequestsWithReviewer;
+pub use self::merge_requests::MergeRequestApprovals;
+pub use self::merge_requests::MergeRequestApprovalState;
+pub use self::merge_requests::CancelMergeWhenPipelineSucceeds;
 pub use gitlab::api::projects::merge_requests::CreateMergeRequest;
 pub use gitlab::api::projects::merge_requests::CreateMergeRequestBuilder;
 pub use gitlab::api::projects::merge_requests::UnapproveMergeRequest;
@@ -60,6 +90,36 @@ pub use gitlab::api::projects::repository::branches::CreateBranch;
 pub use gitlab::api::projects::repository::branches::CreateBranchBuilder;
 pub use gitlab::api::projects::repository::branches::Branch;
 pub use gitlab::api::projects::repository::branches::BranchBuilder;
+pub use self::branches::Branches;
+pub use self::branches::DeleteBranch;
+pub use self::branches::ProtectedBranches;
+pub use gitlab::api::projects::protected_branches::ProtectBranch;
+pub use gitlab::api::projects::protected_branches::UnprotectBranch;
+pub use gitlab::api::common::ProtectedAccessLevel;
+
+pub use self::repository::RepositoryTree;
+pub use self::repository::RawFile;
+
+pub use gitlab::api::groups::projects::GroupProjects;
+pub use gitlab::api::groups::projects::GroupProjectsBuilder;
+pub use gitlab::api::groups::projects::GroupProjectsOrderBy;
+
+pub use gitlab::api::users::CurrentUser;
+
+pub use self::time_tracking::TimeTrackable;
+pub use self::time_tracking::AddSpentTime;
+pub use self::time_tracking::SetTimeEstimate;
+pub use self::time_tracking::ResetSpentTime;
+pub use self::time_tracking::ResetTimeEstimate;
+pub use self::time_tracking::TimeStats;
+
+pub use self::todos::Todos;
+pub use self::todos::TodosBuilder;
+pub use self::todos::TodoState;
+pub use self::todos::TodoAction;
+pub use self::todos::TodoTargetType;
+pub use self::todos::MarkTodoAsDone;
+pub use self::todos::MarkAllTodosAsDone;
 
 pub use gitlab::api::common::EnableState;
 pub use gitlab::api::common::VisibilityLevel;
@@ -137,6 +197,39 @@ pub mod converter {
         }
     }
 
+    pub fn issue_link_type_from_str(s: &str) -> Result<IssueLinkType> {
+        match s {
+            "relates_to" => Ok(IssueLinkType::RelatesTo),
+            "blocks" => Ok(IssueLinkType::Blocks),
+            "is_blocked_by" => Ok(IssueLinkType::IsBlockedBy),
+            _ => Err(anyhow!("Incorrect issue link type"))
+        }
+    }
+
+    pub fn project_order_by_from_str(s: &str) -> Result<ProjectOrderBy> {
+        match s {
+            "id" => Ok(ProjectOrderBy::Id),
+            "name" => Ok(ProjectOrderBy::Name),
+            "path" => Ok(ProjectOrderBy::Path),
+            "created_on" => Ok(ProjectOrderBy::CreatedAt),
+            "updated_on" => Ok(ProjectOrderBy::UpdatedAt),
+            "last_activity_on" => Ok(ProjectOrderBy::LastActivityAt),
+            _ => Err(anyhow!("Incorrect project list ordering"))
+        }
+    }
+
+    pub fn group_projects_order_by_from_str(s: &str) -> Result<GroupProjectsOrderBy> {
+        match s {
+            "id" => Ok(GroupProjectsOrderBy::Id),
+            "name" => Ok(GroupProjectsOrderBy::Name),
+            "path" => Ok(GroupProjectsOrderBy::Path),
+            "created_on" => Ok(GroupProjectsOrderBy::CreatedAt),
+            "updated_on" => Ok(GroupProjectsOrderBy::UpdatedAt),
+            "last_activity_on" => Ok(GroupProjectsOrderBy::LastActivityAt),
+            _ => Err(anyhow!("Incorrect group project list ordering"))
+        }
+    }
+
     pub fn auto_devops_deploy_strategy_from_str(s: &str) -> Result<AutoDevOpsDeployStrategy> {
         match s {
             "continuous" => Ok(AutoDevOpsDeployStrategy::Continuous),
@@ -197,6 +290,46 @@ pub mod converter {
             _ => Err(anyhow!("Incorrect feature access level"))
         }
     }
+
+    pub fn todo_state_from_str(s: &str) -> Result<TodoState> {
+        match s {
+            "pending" => Ok(TodoState::Pending),
+            "done" => Ok(TodoState::Done),
+            _ => Err(anyhow!("Incorrect to-do state"))
+        }
+    }
+
+    pub fn todo_action_from_str(s: &str) -> Result<TodoAction> {
+        match s {
+            "assigned" => Ok(TodoAction::Assigned),
+            "mentioned" => Ok(TodoAction::Mentioned),
+            "build_failed" => Ok(TodoAction::BuildFailed),
+            "marked" => Ok(TodoAction::Marked),
+            "approval_required" => Ok(TodoAction::ApprovalRequired),
+            "unmergeable" => Ok(TodoAction::Unmergeable),
+            "directly_addressed" => Ok(TodoAction::DirectlyAddressed),
+            "review_requested" => Ok(TodoAction::ReviewRequested),
+            _ => Err(anyhow!("Incorrect to-do action"))
+        }
+    }
+
+    pub fn todo_target_type_from_str(s: &str) -> Result<TodoTargetType> {
+        match s {
+            "issue" => Ok(TodoTargetType::Issue),
+            "mr" => Ok(TodoTargetType::MergeRequest),
+            _ => Err(anyhow!("Incorrect to-do type"))
+        }
+    }
+
+    pub fn protected_access_level_from_str(s: &str) -> Result<ProtectedAccessLevel> {
+        match s {
+            "developer" => Ok(ProtectedAccessLevel::Developer),
+            "maintainer" => Ok(ProtectedAccessLevel::Maintainer),
+            "admin" => Ok(ProtectedAccessLevel::Admin),
+            "no_access" => Ok(ProtectedAccessLevel::NoAccess),
+            _ => Err(anyhow!("Incorrect access level"))
+        }
+    }
 }
 
 /// Shim over 3rd party new() method
@@ -259,6 +392,24 @@ mod gitlab_converter_unit_tests {
         case("opened", IssueState::Opened, &issue_state_from_str),
         case("closed", IssueState::Closed, &issue_state_from_str),
 
+        case("relates_to", IssueLinkType::RelatesTo, &issue_link_type_from_str),
+        case("blocks", IssueLinkType::Blocks, &issue_link_type_from_str),
+        case("is_blocked_by", IssueLinkType::IsBlockedBy, &issue_link_type_from_str),
+
+        case("id", ProjectOrderBy::Id, &project_order_by_from_str),
+        case("name", ProjectOrderBy::Name, &project_order_by_from_str),
+        case("path", ProjectOrderBy::Path, &project_order_by_from_str),
+        case("created_on", ProjectOrderBy::CreatedAt, &project_order_by_from_str),
+        case("updated_on", ProjectOrderBy::UpdatedAt, &project_order_by_from_str),
+        case("last_activity_on", ProjectOrderBy::LastActivityAt, &project_order_by_from_str),
+
+        case("id", GroupProjectsOrderBy::Id, &group_projects_order_by_from_str),
+        case("name", GroupProjectsOrderBy::Name, &group_projects_order_by_from_str),
+        case("path", GroupProjectsOrderBy::Path, &group_projects_order_by_from_str),
+        case("created_on", GroupProjectsOrderBy::CreatedAt, &group_projects_order_by_from_str),
+        case("updated_on", GroupProjectsOrderBy::UpdatedAt, &group_projects_order_by_from_str),
+        case("last_activity_on", GroupProjectsOrderBy::LastActivityAt, &group_projects_order_by_from_str),
+
         case("continuous", AutoDevOpsDeployStrategy::Continuous, &auto_devops_deploy_strategy_from_str),
         case("manual", AutoDevOpsDeployStrategy::Manual, &auto_devops_deploy_strategy_from_str),
         case("timed_incremental", AutoDevOpsDeployStrategy::TimedIncremental, &auto_devops_deploy_strategy_from_str),
@@ -285,6 +436,26 @@ mod gitlab_converter_unit_tests {
         case("disabled", FeatureAccessLevel::Disabled, &feature_access_level_from_str),
         case("private", FeatureAccessLevel::Private, &feature_access_level_from_str),
         case("enabled", FeatureAccessLevel::Enabled, &feature_access_level_from_str),
+
+        case("pending", TodoState::Pending, &todo_state_from_str),
+        case("done", TodoState::Done, &todo_state_from_str),
+
+        case("assigned", TodoAction::Assigned, &todo_action_from_str),
+        case("mentioned", TodoAction::Mentioned, &todo_action_from_str),
+        case("build_failed", TodoAction::BuildFailed, &todo_action_from_str),
+        case("marked", TodoAction::Marked, &todo_action_from_str),
+        case("approval_required", TodoAction::ApprovalRequired, &todo_action_from_str),
+        case("unmergeable", TodoAction::Unmergeable, &todo_action_from_str),
+        case("directly_addressed", TodoAction::DirectlyAddressed, &todo_action_from_str),
+        case("review_requested", TodoAction::ReviewRequested, &todo_action_from_str),
+
+        case("issue", TodoTargetType::Issue, &todo_target_type_from_str),
+        case("mr", TodoTargetType::MergeRequest, &todo_target_type_from_str),
+
+        case("developer", ProtectedAccessLevel::Developer, &protected_access_level_from_str),
+        case("maintainer", ProtectedAccessLevel::Maintainer, &protected_access_level_from_str),
+        case("admin", ProtectedAccessLevel::Admin, &protected_access_level_from_str),
+        case("no_access", ProtectedAccessLevel::NoAccess, &protected_access_level_from_str),
     )]
     fn test_gitlab_converter_from_str_ok<T>(s: &str, t: T, f: &dyn Fn(&str) -> Result<T>)
     where T: Eq + std::fmt::Debug
@@ -297,6 +468,9 @@ mod gitlab_converter_unit_tests {
         case("blah", &issue_order_by_from_str),
         case("blah", &issue_scope_from_str),
         case("blah", &issue_state_from_str),
+        case("blah", &issue_link_type_from_str),
+        case("blah", &project_order_by_from_str),
+        case("blah", &group_projects_order_by_from_str),
         case("blah", &auto_devops_deploy_strategy_from_str),
         case("blah", &enable_state_from_str),
         case("blah", &pipeline_git_strategy_from_str),
@@ -304,6 +478,10 @@ mod gitlab_converter_unit_tests {
         case("blah", &visibility_level_from_str),
         case("blah", &feature_access_level_public_from_str),
         case("blah", &feature_access_level_from_str),
+        case("blah", &todo_state_from_str),
+        case("blah", &todo_action_from_str),
+        case("blah", &todo_target_type_from_str),
+        case("blah", &protected_access_level_from_str),
     )]
     fn test_gitlab_converter_from_str_err<T>(s: &str,  f: &dyn Fn(&str) -> Result<T>)
     where T: Eq + std::fmt::Debug
@@ -0,0 +1,108 @@
+//! Repository branch endpoints not (yet) provided by the 3rd party GitLab library.
+use derive_builder::Builder;
+
+use gitlab::api::common::{self, NameOrId};
+use gitlab::api::endpoint_prelude::*;
+
+/// Query the branches of a project's repository.
+///
+/// Unlike the library's `Branches`, this can be paged through.
+#[derive(Debug, Builder)]
+#[builder(setter(strip_option))]
+pub struct Branches<'a> {
+    /// The project of the branches.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// Filter branches by a search query.
+    #[builder(setter(into), default)]
+    search: Option<Cow<'a, str>>,
+}
+
+impl<'a> Branches<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> BranchesBuilder<'a> {
+        BranchesBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Branches<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/repository/branches", self.project).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+
+        params.push_opt("search", self.search.as_ref());
+
+        params
+    }
+}
+
+impl<'a> Pageable for Branches<'a> {}
+
+/// Delete a branch of a project's repository.
+#[derive(Debug, Builder)]
+pub struct DeleteBranch<'a> {
+    /// The project of the branch.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// The name of the branch.
+    #[builder(setter(into))]
+    branch: Cow<'a, str>,
+}
+
+impl<'a> DeleteBranch<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> DeleteBranchBuilder<'a> {
+        DeleteBranchBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for DeleteBranch<'a> {
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!(
+            "projects/{}/repository/branches/{}",
+            self.project,
+            common::path_escaped(&self.branch),
+        )
+        .into()
+    }
+}
+
+/// Query the protected branches of a project.
+///
+/// Unlike the library's `ProtectedBranches`, this can be paged through.
+#[derive(Debug, Builder)]
+pub struct ProtectedBranches<'a> {
+    /// The project of the protected branches.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+}
+
+impl<'a> ProtectedBranches<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> ProtectedBranchesBuilder<'a> {
+        ProtectedBranchesBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for ProtectedBranches<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/protected_branches", self.project).into()
+    }
+}
+
+impl<'a> Pageable for ProtectedBranches<'a> {}
@@ -0,0 +1,322 @@
+//! Issue endpoints not (yet) provided by the 3rd party GitLab library.
+use std::collections::BTreeSet;
+
+use chrono::NaiveDate;
+use derive_builder::Builder;
+
+use gitlab::api::common::NameOrId;
+use gitlab::api::endpoint_prelude::*;
+use gitlab::api::ParamValue;
+
+/// Edit the details of an issue.
+///
+/// Unlike the library's `EditIssue`, this can set confidentiality and add or remove individual
+/// labels, rather than replacing the issue's whole set.
+#[derive(Debug, Builder)]
+#[builder(setter(strip_option))]
+pub struct UpdateIssue<'a> {
+    /// The project of the issue.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// The internal ID of the issue.
+    issue: u64,
+
+    /// The title of the issue.
+    #[builder(setter(into), default)]
+    title: Option<Cow<'a, str>>,
+    /// The description of the issue.
+    #[builder(setter(into), default)]
+    description: Option<Cow<'a, str>>,
+    /// Whether the issue is confidential.
+    #[builder(default)]
+    confidential: Option<bool>,
+    /// The ID of the milestone for the issue. Zero removes the milestone.
+    #[builder(default)]
+    milestone_id: Option<u64>,
+    /// Labels to add to the issue.
+    #[builder(setter(name = "_add_labels"), default, private)]
+    add_labels: BTreeSet<Cow<'a, str>>,
+    /// Labels to remove from the issue.
+    #[builder(setter(name = "_remove_labels"), default, private)]
+    remove_labels: BTreeSet<Cow<'a, str>>,
+    /// The due date for the issue.
+    #[builder(default)]
+    due_date: Option<NaiveDate>,
+    /// The weight of the issue.
+    #[builder(default)]
+    weight: Option<u64>,
+    /// Whether to remove the issue's due date.
+    #[builder(default)]
+    clear_due_date: bool,
+    /// Whether to remove the issue's weight.
+    #[builder(default)]
+    clear_weight: bool,
+}
+
+impl<'a> UpdateIssue<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> UpdateIssueBuilder<'a> {
+        UpdateIssueBuilder::default()
+    }
+}
+
+impl<'a> UpdateIssueBuilder<'a> {
+    /// Add labels to the issue, leaving any others it has in place.
+    pub fn add_labels<I, L>(&mut self, iter: I) -> &mut Self
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Cow<'a, str>>,
+    {
+        self.add_labels
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.into_iter().map(Into::into));
+        self
+    }
+
+    /// Remove labels from the issue, leaving any others it has in place.
+    pub fn remove_labels<I, L>(&mut self, iter: I) -> &mut Self
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Cow<'a, str>>,
+    {
+        self.remove_labels
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.into_iter().map(Into::into));
+        self
+    }
+}
+
+impl<'a> Endpoint for UpdateIssue<'a> {
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/issues/{}", self.project, self.issue).into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+
+        params
+            .push_opt("title", self.title.as_ref())
+            .push_opt("description", self.description.as_ref())
+            .push_opt("confidential", self.confidential)
+            .push_opt("milestone_id", self.milestone_id)
+            .push_opt("due_date", self.due_date)
+            .push_opt("weight", self.weight);
+
+        // GitLab takes an empty value as removing these
+        if self.clear_due_date {
+            params.push("due_date", "");
+        }
+
+        if self.clear_weight {
+            params.push("weight", "");
+        }
+
+        if !self.add_labels.is_empty() {
+            params.push("add_labels", self.add_labels.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(","));
+        }
+
+        if !self.remove_labels.is_empty() {
+            params.push("remove_labels", self.remove_labels.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(","));
+        }
+
+        params.into_body()
+    }
+}
+
+/// Types of link between issues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueLinkType {
+    /// The issues are related.
+    RelatesTo,
+    /// The issue blocks the target issue.
+    Blocks,
+    /// The issue is blocked by the target issue.
+    IsBlockedBy,
+}
+
+impl IssueLinkType {
+    fn as_str(self) -> &'static str {
+        match self {
+            IssueLinkType::RelatesTo => "relates_to",
+            IssueLinkType::Blocks => "blocks",
+            IssueLinkType::IsBlockedBy => "is_blocked_by",
+        }
+    }
+}
+
+impl ParamValue<'static> for IssueLinkType {
+    fn as_value(self) -> Cow<'static, str> {
+        self.as_str().into()
+    }
+}
+
+/// Query the issues linked to an issue.
+#[derive(Debug, Builder)]
+pub struct IssueLinks<'a> {
+    /// The project of the issue.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// The internal ID of the issue.
+    issue: u64,
+}
+
+impl<'a> IssueLinks<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> IssueLinksBuilder<'a> {
+        IssueLinksBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for IssueLinks<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/issues/{}/links", self.project, self.issue).into()
+    }
+}
+
+/// Link an issue to another, possibly in a different project.
+#[derive(Debug, Builder)]
+#[builder(setter(strip_option))]
+pub struct CreateIssueLink<'a> {
+    /// The project of the issue.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// The internal ID of the issue.
+    issue: u64,
+    /// The project of the issue to link to.
+    #[builder(setter(into))]
+    target_project: NameOrId<'a>,
+    /// The internal ID of the issue to link to.
+    target_issue: u64,
+    /// The type of link.
+    #[builder(default)]
+    link_type: Option<IssueLinkType>,
+}
+
+impl<'a> CreateIssueLink<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> CreateIssueLinkBuilder<'a> {
+        CreateIssueLinkBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for CreateIssueLink<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/issues/{}/links", self.project, self.issue).into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+
+        // the project is sent as a form value, so mustn't be URL-encoded as it is in a path
+        let target_project = match &self.target_project {
+            NameOrId::Name(name) => name.to_string(),
+            NameOrId::Id(id) => id.to_string(),
+        };
+
+        params
+            .push("target_project_id", target_project)
+            .push("target_issue_iid", self.target_issue)
+            .push_opt("link_type", self.link_type);
+
+        params.into_body()
+    }
+}
+
+/// Remove a link between issues.
+#[derive(Debug, Builder)]
+pub struct DeleteIssueLink<'a> {
+    /// The project of the issue.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// The internal ID of the issue.
+    issue: u64,
+    /// The ID of the link, as opposed to that of either issue.
+    issue_link: u64,
+}
+
+impl<'a> DeleteIssueLink<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> DeleteIssueLinkBuilder<'a> {
+        DeleteIssueLinkBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for DeleteIssueLink<'a> {
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/issues/{}/links/{}", self.project, self.issue, self.issue_link).into()
+    }
+}
+
+/// Query the merge requests which mention an issue, in any project.
+#[derive(Debug, Builder)]
+pub struct RelatedMergeRequests<'a> {
+    /// The project of the issue.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// The internal ID of the issue.
+    issue: u64,
+}
+
+impl<'a> RelatedMergeRequests<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> RelatedMergeRequestsBuilder<'a> {
+        RelatedMergeRequestsBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for RelatedMergeRequests<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/issues/{}/related_merge_requests", self.project, self.issue).into()
+    }
+}
+
+impl<'a> Pageable for RelatedMergeRequests<'a> {}
+
+/// Query the merge requests which will close an issue when merged.
+#[derive(Debug, Builder)]
+pub struct MergeRequestsClosingIssue<'a> {
+    /// The project of the issue.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// The internal ID of the issue.
+    issue: u64,
+}
+
+impl<'a> MergeRequestsClosingIssue<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> MergeRequestsClosingIssueBuilder<'a> {
+        MergeRequestsClosingIssueBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for MergeRequestsClosingIssue<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/issues/{}/closed_by", self.project, self.issue).into()
+    }
+}
+
+impl<'a> Pageable for MergeRequestsClosingIssue<'a> {}
@@ -0,0 +1,254 @@
+//! Merge request endpoints not (yet) provided by the 3rd party GitLab library.
+use std::collections::BTreeSet;
+
+use derive_builder::Builder;
+
+use gitlab::api::common::NameOrId;
+use gitlab::api::endpoint_prelude::*;
+use gitlab::api::projects::merge_requests::MergeRequests;
+
+/// Edit the details of a merge request.
+///
+/// Unlike the library's `EditMergeRequest`, this can set reviewers and add or remove individual
+/// labels, rather than replacing the merge request's whole set.
+#[derive(Debug, Builder)]
+#[builder(setter(strip_option))]
+pub struct UpdateMergeRequest<'a> {
+    /// The project of the merge request.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// The internal ID of the merge request.
+    merge_request: u64,
+
+    /// The title of the merge request.
+    #[builder(setter(into), default)]
+    title: Option<Cow<'a, str>>,
+    /// The description of the merge request.
+    #[builder(setter(into), default)]
+    description: Option<Cow<'a, str>>,
+    /// The branch to merge into.
+    #[builder(setter(into), default)]
+    target_branch: Option<Cow<'a, str>>,
+    /// The ID of the milestone for the merge request. Zero removes the milestone.
+    #[builder(default)]
+    milestone_id: Option<u64>,
+    /// Labels to add to the merge request.
+    #[builder(setter(name = "_add_labels"), default, private)]
+    add_labels: BTreeSet<Cow<'a, str>>,
+    /// Labels to remove from the merge request.
+    #[builder(setter(name = "_remove_labels"), default, private)]
+    remove_labels: BTreeSet<Cow<'a, str>>,
+    /// The users to review the merge request. An empty set removes all reviewers.
+    #[builder(setter(name = "_reviewer_ids"), default, private)]
+    reviewer_ids: Option<BTreeSet<u64>>,
+    /// Whether to squash the branch when merging.
+    #[builder(default)]
+    squash: Option<bool>,
+    /// Whether to remove the source branch once merged.
+    #[builder(default)]
+    remove_source_branch: Option<bool>,
+}
+
+impl<'a> UpdateMergeRequest<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> UpdateMergeRequestBuilder<'a> {
+        UpdateMergeRequestBuilder::default()
+    }
+}
+
+impl<'a> UpdateMergeRequestBuilder<'a> {
+    /// Add labels to the merge request, leaving any others it has in place.
+    pub fn add_labels<I, L>(&mut self, iter: I) -> &mut Self
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Cow<'a, str>>,
+    {
+        self.add_labels
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.into_iter().map(Into::into));
+        self
+    }
+
+    /// Remove labels from the merge request, leaving any others it has in place.
+    pub fn remove_labels<I, L>(&mut self, iter: I) -> &mut Self
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Cow<'a, str>>,
+    {
+        self.remove_labels
+            .get_or_insert_with(BTreeSet::new)
+            .extend(iter.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the reviewers of the merge request, replacing any it has.
+    pub fn reviewer_ids<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        self.reviewer_ids = Some(Some(iter.into_iter().collect()));
+        self
+    }
+}
+
+impl<'a> Endpoint for UpdateMergeRequest<'a> {
+    fn method(&self) -> Method {
+        Method::PUT
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/merge_requests/{}", self.project, self.merge_request).into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+
+        params
+            .push_opt("title", self.title.as_ref())
+            .push_opt("description", self.description.as_ref())
+            .push_opt("target_branch", self.target_branch.as_ref())
+            .push_opt("milestone_id", self.milestone_id)
+            .push_opt("squash", self.squash)
+            .push_opt("remove_source_branch", self.remove_source_branch);
+
+        if !self.add_labels.is_empty() {
+            params.push("add_labels", self.add_labels.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(","));
+        }
+
+        if !self.remove_labels.is_empty() {
+            params.push("remove_labels", self.remove_labels.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(","));
+        }
+
+        match self.reviewer_ids.as_ref() {
+            Some(ids) if ids.is_empty() => {
+                params.push("reviewer_ids[]", "0");
+            },
+            Some(ids) => {
+                params.extend(ids.iter().map(|&value| ("reviewer_ids[]", value)));
+            },
+            None => (),
+        }
+
+        params.into_body()
+    }
+}
+
+/// List merge requests, optionally filtered by reviewer, which the library's `MergeRequests`
+/// cannot do.
+#[derive(Debug)]
+pub struct MergeRequestsWithReviewer<'a> {
+    merge_requests: MergeRequests<'a>,
+    reviewer_id: Option<u64>,
+}
+
+impl<'a> MergeRequestsWithReviewer<'a> {
+    /// Wrap a merge requests query, restricting it to those that the given user is to review.
+    pub fn new(merge_requests: MergeRequests<'a>, reviewer_id: Option<u64>) -> Self {
+        MergeRequestsWithReviewer { merge_requests, reviewer_id }
+    }
+}
+
+impl<'a> Endpoint for MergeRequestsWithReviewer<'a> {
+    fn method(&self) -> Method {
+        self.merge_requests.method()
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        self.merge_requests.endpoint()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = self.merge_requests.parameters();
+
+        params.push_opt("reviewer_id", self.reviewer_id);
+
+        params
+    }
+}
+
+impl<'a> Pageable for MergeRequestsWithReviewer<'a> {}
+
+/// Query the approval state of a merge request.
+#[derive(Debug, Builder)]
+pub struct MergeRequestApprovals<'a> {
+    /// The project of the merge request.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// The internal ID of the merge request.
+    merge_request: u64,
+}
+
+impl<'a> MergeRequestApprovals<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> MergeRequestApprovalsBuilder<'a> {
+        MergeRequestApprovalsBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for MergeRequestApprovals<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/merge_requests/{}/approvals", self.project, self.merge_request).into()
+    }
+}
+
+/// Query the state of a merge request's approval rules.
+#[derive(Debug, Builder)]
+pub struct MergeRequestApprovalState<'a> {
+    /// The project of the merge request.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// The internal ID of the merge request.
+    merge_request: u64,
+}
+
+impl<'a> MergeRequestApprovalState<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> MergeRequestApprovalStateBuilder<'a> {
+        MergeRequestApprovalStateBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for MergeRequestApprovalState<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/merge_requests/{}/approval_state", self.project, self.merge_request).into()
+    }
+}
+
+/// Cancel a merge request's merge when its pipeline succeeds.
+#[derive(Debug, Builder)]
+pub struct CancelMergeWhenPipelineSucceeds<'a> {
+    /// The project of the merge request.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// The internal ID of the merge request.
+    merge_request: u64,
+}
+
+impl<'a> CancelMergeWhenPipelineSucceeds<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> CancelMergeWhenPipelineSucceedsBuilder<'a> {
+        CancelMergeWhenPipelineSucceedsBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for CancelMergeWhenPipelineSucceeds<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!(
+            "projects/{}/merge_requests/{}/cancel_merge_when_pipeline_succeeds",
+            self.project, self.merge_request,
+        )
+        .into()
+    }
+}
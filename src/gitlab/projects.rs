@@ -0,0 +1,167 @@
+//! Project endpoints not (yet) provided by the 3rd party GitLab library.
+use std::collections::BTreeSet;
+
+use derive_builder::Builder;
+
+use gitlab::api::common::NameOrId;
+use gitlab::api::endpoint_prelude::*;
+
+/// Fork a project into a namespace.
+#[derive(Debug, Builder)]
+#[builder(setter(strip_option))]
+pub struct ForkProject<'a> {
+    /// The project to fork.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+
+    /// The ID of the namespace to fork into. Defaults to the user's namespace.
+    #[builder(default)]
+    namespace_id: Option<u64>,
+    /// The path of the namespace to fork into. Defaults to the user's namespace.
+    #[builder(setter(into), default)]
+    namespace_path: Option<Cow<'a, str>>,
+    /// The path to give the forked project.
+    #[builder(setter(into), default)]
+    path: Option<Cow<'a, str>>,
+    /// The name to give the forked project.
+    #[builder(setter(into), default)]
+    name: Option<Cow<'a, str>>,
+}
+
+impl<'a> ForkProject<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> ForkProjectBuilder<'a> {
+        ForkProjectBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for ForkProject<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/fork", self.project).into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+
+        params
+            .push_opt("namespace_id", self.namespace_id)
+            .push_opt("namespace_path", self.namespace_path.as_ref())
+            .push_opt("path", self.path.as_ref())
+            .push_opt("name", self.name.as_ref());
+
+        params.into_body()
+    }
+}
+
+/// Query the approval rules of a project.
+#[derive(Debug, Builder)]
+pub struct ProjectApprovalRules<'a> {
+    /// The project of the approval rules.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+}
+
+impl<'a> ProjectApprovalRules<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> ProjectApprovalRulesBuilder<'a> {
+        ProjectApprovalRulesBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for ProjectApprovalRules<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/approval_rules", self.project).into()
+    }
+}
+
+impl<'a> Pageable for ProjectApprovalRules<'a> {}
+
+/// Create an approval rule for a project.
+#[derive(Debug, Builder)]
+pub struct CreateProjectApprovalRule<'a> {
+    /// The project to create the approval rule in.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// The name of the approval rule.
+    #[builder(setter(into))]
+    name: Cow<'a, str>,
+    /// The number of approvals required by the rule.
+    approvals_required: u64,
+
+    /// The users who can approve.
+    #[builder(setter(name = "_user_ids"), default, private)]
+    user_ids: BTreeSet<u64>,
+    /// The groups whose members can approve.
+    #[builder(setter(name = "_group_ids"), default, private)]
+    group_ids: BTreeSet<u64>,
+    /// The protected branches the rule applies to, rather than all branches.
+    #[builder(setter(name = "_protected_branch_ids"), default, private)]
+    protected_branch_ids: BTreeSet<u64>,
+}
+
+impl<'a> CreateProjectApprovalRule<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> CreateProjectApprovalRuleBuilder<'a> {
+        CreateProjectApprovalRuleBuilder::default()
+    }
+}
+
+impl<'a> CreateProjectApprovalRuleBuilder<'a> {
+    /// Add users who can approve.
+    pub fn user_ids<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        self.user_ids.get_or_insert_with(BTreeSet::new).extend(iter);
+        self
+    }
+
+    /// Add groups whose members can approve.
+    pub fn group_ids<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        self.group_ids.get_or_insert_with(BTreeSet::new).extend(iter);
+        self
+    }
+
+    /// Add protected branches the rule applies to.
+    pub fn protected_branch_ids<I>(&mut self, iter: I) -> &mut Self
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        self.protected_branch_ids.get_or_insert_with(BTreeSet::new).extend(iter);
+        self
+    }
+}
+
+impl<'a> Endpoint for CreateProjectApprovalRule<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/approval_rules", self.project).into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+
+        params
+            .push("name", &self.name)
+            .push("approvals_required", self.approvals_required)
+            .extend(self.user_ids.iter().map(|&value| ("user_ids[]", value)))
+            .extend(self.group_ids.iter().map(|&value| ("group_ids[]", value)))
+            .extend(self.protected_branch_ids.iter().map(|&value| ("protected_branch_ids[]", value)));
+
+        params.into_body()
+    }
+}
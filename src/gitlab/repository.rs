@@ -0,0 +1,96 @@
+//! Repository tree and file endpoints not (yet) provided by the 3rd party GitLab library.
+use derive_builder::Builder;
+
+use gitlab::api::common::{self, NameOrId};
+use gitlab::api::endpoint_prelude::*;
+
+/// Query the files and directories in a project's repository.
+#[derive(Debug, Builder)]
+#[builder(setter(strip_option))]
+pub struct RepositoryTree<'a> {
+    /// The project of the repository.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// The directory to list, defaulting to the root of the repository.
+    #[builder(setter(into), default)]
+    path: Option<Cow<'a, str>>,
+    /// The branch, tag or commit to list, defaulting to the default branch.
+    #[builder(setter(into), default)]
+    ref_: Option<Cow<'a, str>>,
+}
+
+impl<'a> RepositoryTree<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> RepositoryTreeBuilder<'a> {
+        RepositoryTreeBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for RepositoryTree<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/repository/tree", self.project).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+
+        params
+            .push_opt("path", self.path.as_ref())
+            .push_opt("ref", self.ref_.as_ref());
+
+        params
+    }
+}
+
+impl<'a> Pageable for RepositoryTree<'a> {}
+
+/// Get the raw contents of a file in a project's repository.
+///
+/// The response is not JSON, so query this with `api::raw`.
+#[derive(Debug, Builder)]
+#[builder(setter(strip_option))]
+pub struct RawFile<'a> {
+    /// The project of the repository.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// The path of the file in the repository.
+    #[builder(setter(into))]
+    file_path: Cow<'a, str>,
+    /// The branch, tag or commit to get the file from, defaulting to the default branch.
+    #[builder(setter(into), default)]
+    ref_: Option<Cow<'a, str>>,
+}
+
+impl<'a> RawFile<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> RawFileBuilder<'a> {
+        RawFileBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for RawFile<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!(
+            "projects/{}/repository/files/{}/raw",
+            self.project,
+            common::path_escaped(&self.file_path),
+        )
+        .into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+
+        params.push_opt("ref", self.ref_.as_ref());
+
+        params
+    }
+}
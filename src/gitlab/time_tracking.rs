@@ -0,0 +1,199 @@
+//! Time tracking endpoints not (yet) provided by the 3rd party GitLab library.
+//!
+//! Time is tracked against issues and merge requests in the same way, so these endpoints serve
+//! both.
+use derive_builder::Builder;
+
+use gitlab::api::common::NameOrId;
+use gitlab::api::endpoint_prelude::*;
+
+/// The kinds of object that time can be tracked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeTrackable {
+    /// Track time against an issue.
+    Issue,
+    /// Track time against a merge request.
+    MergeRequest,
+}
+
+impl TimeTrackable {
+    fn as_str(self) -> &'static str {
+        match self {
+            TimeTrackable::Issue => "issues",
+            TimeTrackable::MergeRequest => "merge_requests",
+        }
+    }
+}
+
+/// Add time spent on an issue or merge request.
+#[derive(Debug, Builder)]
+#[builder(setter(strip_option))]
+pub struct AddSpentTime<'a> {
+    /// The project of the issue or merge request.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// Whether time is being added to an issue or a merge request.
+    kind: TimeTrackable,
+    /// The internal ID of the issue or merge request.
+    iid: u64,
+    /// The time spent, e.g., `1h30m`. A negative duration subtracts time.
+    #[builder(setter(into))]
+    duration: Cow<'a, str>,
+    /// A summary of the work done.
+    #[builder(setter(into), default)]
+    summary: Option<Cow<'a, str>>,
+}
+
+impl<'a> AddSpentTime<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> AddSpentTimeBuilder<'a> {
+        AddSpentTimeBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for AddSpentTime<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/{}/{}/add_spent_time", self.project, self.kind.as_str(), self.iid).into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+
+        params
+            .push("duration", self.duration.as_ref())
+            .push_opt("summary", self.summary.as_ref());
+
+        params.into_body()
+    }
+}
+
+/// Set the time estimate of an issue or merge request.
+#[derive(Debug, Builder)]
+pub struct SetTimeEstimate<'a> {
+    /// The project of the issue or merge request.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// Whether the estimate is for an issue or a merge request.
+    kind: TimeTrackable,
+    /// The internal ID of the issue or merge request.
+    iid: u64,
+    /// The estimated time, e.g., `3h30m`.
+    #[builder(setter(into))]
+    duration: Cow<'a, str>,
+}
+
+impl<'a> SetTimeEstimate<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> SetTimeEstimateBuilder<'a> {
+        SetTimeEstimateBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for SetTimeEstimate<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/{}/{}/time_estimate", self.project, self.kind.as_str(), self.iid).into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+
+        params.push("duration", self.duration.as_ref());
+
+        params.into_body()
+    }
+}
+
+/// Reset the time spent on an issue or merge request to zero.
+#[derive(Debug, Builder)]
+pub struct ResetSpentTime<'a> {
+    /// The project of the issue or merge request.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// Whether the issue or merge request is being reset.
+    kind: TimeTrackable,
+    /// The internal ID of the issue or merge request.
+    iid: u64,
+}
+
+impl<'a> ResetSpentTime<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> ResetSpentTimeBuilder<'a> {
+        ResetSpentTimeBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for ResetSpentTime<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/{}/{}/reset_spent_time", self.project, self.kind.as_str(), self.iid).into()
+    }
+}
+
+/// Remove the time estimate of an issue or merge request.
+#[derive(Debug, Builder)]
+pub struct ResetTimeEstimate<'a> {
+    /// The project of the issue or merge request.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// Whether the issue or merge request is being reset.
+    kind: TimeTrackable,
+    /// The internal ID of the issue or merge request.
+    iid: u64,
+}
+
+impl<'a> ResetTimeEstimate<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> ResetTimeEstimateBuilder<'a> {
+        ResetTimeEstimateBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for ResetTimeEstimate<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/{}/{}/reset_time_estimate", self.project, self.kind.as_str(), self.iid).into()
+    }
+}
+
+/// Query the time estimate and time spent on an issue or merge request.
+#[derive(Debug, Builder)]
+pub struct TimeStats<'a> {
+    /// The project of the issue or merge request.
+    #[builder(setter(into))]
+    project: NameOrId<'a>,
+    /// Whether the issue or merge request is being queried.
+    kind: TimeTrackable,
+    /// The internal ID of the issue or merge request.
+    iid: u64,
+}
+
+impl<'a> TimeStats<'a> {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> TimeStatsBuilder<'a> {
+        TimeStatsBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for TimeStats<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("projects/{}/{}/{}/time_stats", self.project, self.kind.as_str(), self.iid).into()
+    }
+}
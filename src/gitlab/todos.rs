@@ -0,0 +1,189 @@
+//! To-do endpoints not (yet) provided by the 3rd party GitLab library.
+use derive_builder::Builder;
+
+use gitlab::api::endpoint_prelude::*;
+use gitlab::api::ParamValue;
+
+/// States of to-do items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoState {
+    /// To-do items still to be dealt with.
+    Pending,
+    /// To-do items marked as done.
+    Done,
+}
+
+impl TodoState {
+    fn as_str(self) -> &'static str {
+        match self {
+            TodoState::Pending => "pending",
+            TodoState::Done => "done",
+        }
+    }
+}
+
+impl ParamValue<'static> for TodoState {
+    fn as_value(self) -> Cow<'static, str> {
+        self.as_str().into()
+    }
+}
+
+/// Actions which create to-do items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoAction {
+    /// The user was assigned the target.
+    Assigned,
+    /// The user was mentioned in the target.
+    Mentioned,
+    /// The target's pipeline failed.
+    BuildFailed,
+    /// The user added a to-do item for the target themselves.
+    Marked,
+    /// The user's approval is required on the target.
+    ApprovalRequired,
+    /// The target can no longer be merged.
+    Unmergeable,
+    /// The user was addressed at the start of a comment on the target.
+    DirectlyAddressed,
+    /// The user was asked to review the target.
+    ReviewRequested,
+}
+
+impl TodoAction {
+    fn as_str(self) -> &'static str {
+        match self {
+            TodoAction::Assigned => "assigned",
+            TodoAction::Mentioned => "mentioned",
+            TodoAction::BuildFailed => "build_failed",
+            TodoAction::Marked => "marked",
+            TodoAction::ApprovalRequired => "approval_required",
+            TodoAction::Unmergeable => "unmergeable",
+            TodoAction::DirectlyAddressed => "directly_addressed",
+            TodoAction::ReviewRequested => "review_requested",
+        }
+    }
+}
+
+impl ParamValue<'static> for TodoAction {
+    fn as_value(self) -> Cow<'static, str> {
+        self.as_str().into()
+    }
+}
+
+/// Types of to-do item targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoTargetType {
+    /// The to-do item is for an issue.
+    Issue,
+    /// The to-do item is for a merge request.
+    MergeRequest,
+}
+
+impl TodoTargetType {
+    fn as_str(self) -> &'static str {
+        match self {
+            TodoTargetType::Issue => "Issue",
+            TodoTargetType::MergeRequest => "MergeRequest",
+        }
+    }
+}
+
+impl ParamValue<'static> for TodoTargetType {
+    fn as_value(self) -> Cow<'static, str> {
+        self.as_str().into()
+    }
+}
+
+/// Query the current user's to-do items.
+#[derive(Debug, Builder)]
+#[builder(setter(strip_option))]
+pub struct Todos {
+    /// Filter to-do items by project.
+    #[builder(default)]
+    project_id: Option<u64>,
+    /// Filter to-do items by state.
+    #[builder(default)]
+    state: Option<TodoState>,
+    /// Filter to-do items by the action which created them.
+    #[builder(default)]
+    action: Option<TodoAction>,
+    /// Filter to-do items by the type of their target.
+    #[builder(default)]
+    target_type: Option<TodoTargetType>,
+}
+
+impl Todos {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> TodosBuilder {
+        TodosBuilder::default()
+    }
+}
+
+impl Endpoint for Todos {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "todos".into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+
+        params
+            .push_opt("project_id", self.project_id)
+            .push_opt("state", self.state)
+            .push_opt("action", self.action)
+            .push_opt("type", self.target_type);
+
+        params
+    }
+}
+
+impl Pageable for Todos {}
+
+/// Mark one of the current user's to-do items as done.
+#[derive(Debug, Builder)]
+pub struct MarkTodoAsDone {
+    /// The ID of the to-do item.
+    todo: u64,
+}
+
+impl MarkTodoAsDone {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> MarkTodoAsDoneBuilder {
+        MarkTodoAsDoneBuilder::default()
+    }
+}
+
+impl Endpoint for MarkTodoAsDone {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("todos/{}/mark_as_done", self.todo).into()
+    }
+}
+
+/// Mark all of the current user's pending to-do items as done.
+#[derive(Debug, Builder)]
+pub struct MarkAllTodosAsDone {}
+
+impl MarkAllTodosAsDone {
+    /// Create a builder for the endpoint.
+    pub fn builder() -> MarkAllTodosAsDoneBuilder {
+        MarkAllTodosAsDoneBuilder::default()
+    }
+}
+
+impl Endpoint for MarkAllTodosAsDone {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "todos/mark_as_done".into()
+    }
+}
@@ -20,11 +20,16 @@
 //!  * `project` -- interact with GitLab projects
 //!     * `project create` -- create project
 //!     * `project attach` -- associate a local repo with a project
+//!     * `project clone` -- clone a project and attach it to the new local repo
+//!     * `project fork` -- fork a project and add the fork as a git remote
+//!     * `project list` -- get list of projects
 //!     * `project (open|view|browse)` -- open project's URL in browser
 //!     * `project (show|info|get)` -- show details about a project
+//!     * `project approval-rules (list|create)` -- list or create merge request approval rules
 //!  * `issue` -- interact with issues
 //!     * `issue create` -- create issue (either entirely via cli-passed parameters, or
 //!        interactively, by prompting the user for the inputs needed)
+//!     * `issue edit` -- edit issue (either via cli-passed parameters, or interactively)
 //!     * `issue assign` -- assign issue
 //!     * `issue (open|view|browse)` -- open issue's URL in browser
 //!     * `issue (show|info|get)` -- show details about a issue
@@ -33,10 +38,18 @@
 //!     * `issue reopen` -- reopen issue
 //!     * `issue lock` -- lock discussions on issue
 //!     * `issue unlock` -- unlock discussions on issue
+//!     * `issue branch` -- create and checkout a branch to work on issue
+//!     * `issue (checkout|co)` -- checkout the merge request for an issue
+//!     * `issue link` -- link issue to another, possibly in a different project
+//!     * `issue unlink` -- remove link between issues
+//!     * `issue time` -- track time spent on, and estimated for, issue
 //!  * `mr` -- interact with merge requests
 //!     * `mr create` -- create merge request (either entirely via cli-passed parameters, or
-//!        interactively, by prompting the user for the inputs needed)
+//!        interactively, by prompting the user for the inputs needed). Merge requests are
+//!        created ready for review, unless `--draft` is passed
+//!     * `mr edit` -- edit merge request (either via cli-passed parameters, or interactively)
 //!     * `mr assign` -- assign merge request
+//!     * `mr review-request` -- request review of merge request
 //!     * `mr close` -- close merge request
 //!     * `mr reopen` -- reopen merge request
 //!     * `mr lock` -- lock discussions on merge request
@@ -45,14 +58,30 @@
 //!     * `mr (open|view|browse)` -- open merge request's URL in browser
 //!     * `mr (show|info|get)` -- show details about a merge request
 //!     * `mr (checkout|co)` -- checkout merge request
-//!     * `mr wip` -- toggle `WIP:` (or `Draft:`) status of merge request
+//!     * `mr draft` -- mark merge request as a draft
+//!     * `mr ready` -- mark draft merge request as ready
+//!     * `mr wip` -- toggle draft status of merge request
+//!     * `mr stack` -- show a stack of dependent merge requests, and retarget them once merged
 //!     * `mr approve` -- approve merge request
+//!     * `mr approvals` -- show who has approved a merge request and which approval rules are satisfied
 //!     * `mr merge` -- merge merge request
+//!     * `mr time` -- track time spent on, and estimated for, merge request
+//!  * `todo` -- interact with to-do items
+//!     * `todo list` -- get list of to-do items
+//!     * `todo done` -- mark to-do item(s) as done
+//!     * `todo done-all` -- mark all pending to-do items as done
+//!  * `branch` -- interact with branches
+//!     * `branch list` -- get list of branches, or of protected branches
+//!     * `branch create` -- create branch
+//!     * `branch delete` -- delete branch(es)
+//!     * `branch protect` -- protect branch(es), setting who may push and merge
+//!     * `branch unprotect` -- unprotect branch(es)
+//!     * `branch prune` -- delete local branches whose merge requests are merged or closed
+//!  * `(status|dashboard)` -- show merge requests, issues and to-do items needing your attention
 //!
 //! ## Planned functions
 //!
 //!  * `labels` -- interact with Gitlab labels
-//!  * `project list` -- get list of projects
 //!  * `pipeline` -- interact with Gitlab CI jobs
 //!  * `group` -- interact with Gitlab groups
 //!  * `user` -- interact with Gitlab users
@@ -145,17 +174,22 @@ mod utils;
 mod gitlab;
 
 mod cmds {
+    pub mod branch;
     pub mod init;
     pub mod issue;
     pub mod mr;
     pub mod project;
+    pub mod status;
+    pub mod templates;
+    pub mod time_tracking;
+    pub mod todo;
 }
 
 use anyhow::{anyhow, Result};
 
 use config::Config;
 
-use crate::cmds::{init, mr, project, issue};
+use crate::cmds::{branch, init, mr, project, issue, status, todo};
 
 /// This should be called before calling any cli method or printing any output.
 /// See https://github.com/rust-lang/rust/issues/46016#issuecomment-605624865
@@ -192,6 +226,15 @@ fn main() -> Result<()> {
             Box::new(project::ProjectCmd {
                 clap_cmd: clap::SubCommand::with_name("project"),
             }),
+            Box::new(status::StatusCmd {
+                clap_cmd: clap::SubCommand::with_name("status"),
+            }),
+            Box::new(todo::TodoCmd {
+                clap_cmd: clap::SubCommand::with_name("todo"),
+            }),
+            Box::new(branch::BranchCmd {
+                clap_cmd: clap::SubCommand::with_name("branch"),
+            }),
         ],
     };
 
@@ -227,6 +270,9 @@ fn main() -> Result<()> {
         ("mr", Some(sub_args)) => cli_commands.commands[1].run(config, sub_args.clone())?,
         ("issue", Some(sub_args)) => cli_commands.commands[2].run(config, sub_args.clone())?,
         ("project", Some(sub_args)) => cli_commands.commands[3].run(config, sub_args.clone())?,
+        ("status", Some(sub_args)) => cli_commands.commands[4].run(config, sub_args.clone())?,
+        ("todo", Some(sub_args)) => cli_commands.commands[5].run(config, sub_args.clone())?,
+        ("branch", Some(sub_args)) => cli_commands.commands[6].run(config, sub_args.clone())?,
         _ => (), // clap should catch this before it ever fires
     }
     Ok(())